@@ -0,0 +1,285 @@
+//! Accessibility audits using [axe-core](https://github.com/dequelabs/axe-core).
+//!
+//! The axe-core script is not bundled with thirtyfour and nothing is downloaded.
+//! You need to supply the contents of `axe.min.js` yourself, for example from the
+//! `axe-core` npm package.
+//!
+//! # Example:
+//! ```ignore
+//! use thirtyfour::a11y::Axe;
+//!
+//! let axe = Axe::from_file(driver.handle.clone(), Path::new("node_modules/axe-core/axe.min.js"))?
+//!     .allow_rules(&["color-contrast"]);
+//! let results = axe.audit_page().await?;
+//! results.write_json_report(Path::new("target/a11y.json")).await?;
+//! for violation in &results.violations {
+//!     for node in &violation.nodes {
+//!         if let Some(elem) = &node.element {
+//!             println!("{}: {}", violation.id, elem.outer_html().await?);
+//!         }
+//!     }
+//! }
+//! assert!(results.is_clean());
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::fs::File;
+use tokio::io::AsyncWriteExt;
+
+use crate::error::{WebDriverError, WebDriverResult};
+use crate::session::handle::SessionHandle;
+use crate::WebElement;
+
+/// Injects axe-core (if not already present), runs the audit and returns a trimmed-down
+/// result. The offending DOM element is resolved from the first selector in each node's
+/// `target`, which is only possible for elements in the top-level document.
+const AXE_RUN_SCRIPT: &str = r#"
+    const source = arguments[0];
+    const context = arguments[1] || document;
+    const options = arguments[2];
+    const done = arguments[arguments.length - 1];
+    try {
+        if (!window.axe) {
+            (0, eval)(source);
+        }
+    } catch (e) {
+        done({ error: "failed to load axe-core: " + e });
+        return;
+    }
+    const mapNode = n => {
+        let element = null;
+        if (n.target && n.target.length === 1 && typeof n.target[0] === "string") {
+            element = document.querySelector(n.target[0]);
+        }
+        return {
+            html: n.html,
+            target: n.target,
+            failureSummary: n.failureSummary || null,
+            element: element
+        };
+    };
+    window.axe.run(context, options).then(r => {
+        done({
+            url: r.url,
+            timestamp: r.timestamp,
+            passes: r.passes.length,
+            incomplete: r.incomplete.length,
+            violations: r.violations.map(v => ({
+                id: v.id,
+                impact: v.impact || null,
+                description: v.description,
+                help: v.help,
+                helpUrl: v.helpUrl,
+                tags: v.tags,
+                nodes: v.nodes.map(mapNode)
+            }))
+        });
+    }).catch(e => done({ error: "axe.run() failed: " + e }));
+"#;
+
+/// Runs axe-core accessibility audits against the current page or a single element.
+#[derive(Clone)]
+pub struct Axe {
+    pub handle: SessionHandle,
+    source: Arc<String>,
+    allowed_rules: Vec<String>,
+    options: Value,
+}
+
+impl Axe {
+    /// Create a new Axe struct using the specified axe-core script source.
+    pub fn new(handle: SessionHandle, source: impl Into<String>) -> Self {
+        Self {
+            handle,
+            source: Arc::new(source.into()),
+            allowed_rules: Vec::new(),
+            options: json!({}),
+        }
+    }
+
+    /// Create a new Axe struct, loading the axe-core script from the specified local file.
+    pub fn from_file(handle: SessionHandle, path: &Path) -> WebDriverResult<Self> {
+        let source = std::fs::read_to_string(path)?;
+        Ok(Self::new(handle, source))
+    }
+
+    /// Allow violations of the specified rule ids. Allowed violations are still reported
+    /// in `AxeResults::allowed`, but do not count towards `AxeResults::is_clean()`.
+    pub fn allow_rules<S: AsRef<str>>(mut self, rule_ids: &[S]) -> Self {
+        self.allowed_rules.extend(rule_ids.iter().map(|x| x.as_ref().to_string()));
+        self
+    }
+
+    /// Set the options object passed to `axe.run()`, e.g. `json!({"runOnly": ["wcag2a"]})`.
+    ///
+    /// See the [axe-core API docs](https://github.com/dequelabs/axe-core/blob/develop/doc/API.md#options-parameter)
+    /// for the available options.
+    pub fn with_options(mut self, options: Value) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Audit the whole page.
+    pub async fn audit_page(&self) -> WebDriverResult<AxeResults> {
+        self.run(Value::Null).await
+    }
+
+    /// Audit only the specified element and its descendants.
+    pub async fn audit_element(&self, element: &WebElement) -> WebDriverResult<AxeResults> {
+        self.run(element.to_json()?).await
+    }
+
+    async fn run(&self, context: Value) -> WebDriverResult<AxeResults> {
+        let args = vec![json!(self.source.as_str()), context, self.options.clone()];
+        let ret = self.handle.execute_script_async(AXE_RUN_SCRIPT, args).await?;
+        let raw: RawResults = ret.convert()?;
+        if let Some(e) = raw.error {
+            return Err(WebDriverError::CustomError(e));
+        }
+
+        let mut results = AxeResults {
+            url: raw.url,
+            timestamp: raw.timestamp,
+            passes: raw.passes,
+            incomplete: raw.incomplete,
+            violations: Vec::new(),
+            allowed: Vec::new(),
+        };
+        for v in raw.violations {
+            let violation = v.into_violation(&self.handle)?;
+            if self.allowed_rules.contains(&violation.id) {
+                results.allowed.push(violation);
+            } else {
+                results.violations.push(violation);
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// The results of an axe-core audit.
+#[derive(Debug, Clone, Serialize)]
+pub struct AxeResults {
+    pub url: String,
+    pub timestamp: String,
+    /// The number of rules that passed.
+    pub passes: usize,
+    /// The number of rules that axe-core could not decide on and need manual review.
+    pub incomplete: usize,
+    /// Violations of rules that are not on the allow-list.
+    pub violations: Vec<AxeViolation>,
+    /// Violations of rules that are on the allow-list.
+    pub allowed: Vec<AxeViolation>,
+}
+
+impl AxeResults {
+    /// Return true if there are no violations, other than those on the allow-list.
+    pub fn is_clean(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Write these results to the specified file as pretty-printed JSON.
+    pub async fn write_json_report(&self, path: &Path) -> WebDriverResult<()> {
+        let report = serde_json::to_vec_pretty(self)?;
+        let mut file = File::create(path).await?;
+        file.write_all(&report).await?;
+        Ok(())
+    }
+}
+
+/// A single failed axe-core rule, along with all of the nodes that failed it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AxeViolation {
+    pub id: String,
+    /// One of `minor`, `moderate`, `serious` or `critical`.
+    pub impact: Option<String>,
+    pub description: String,
+    pub help: String,
+    pub help_url: String,
+    pub tags: Vec<String>,
+    pub nodes: Vec<AxeNode>,
+}
+
+/// A single node that failed an axe-core rule.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AxeNode {
+    pub html: String,
+    /// The selector(s) identifying this node. Nested arrays are used for shadow DOM.
+    pub target: Vec<Value>,
+    pub failure_summary: Option<String>,
+    /// The offending element, if it could be resolved from the top-level document.
+    #[serde(skip)]
+    pub element: Option<WebElement>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawResults {
+    error: Option<String>,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    timestamp: String,
+    #[serde(default)]
+    passes: usize,
+    #[serde(default)]
+    incomplete: usize,
+    #[serde(default)]
+    violations: Vec<RawViolation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawViolation {
+    id: String,
+    impact: Option<String>,
+    description: String,
+    help: String,
+    help_url: String,
+    tags: Vec<String>,
+    nodes: Vec<RawNode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawNode {
+    html: String,
+    target: Vec<Value>,
+    failure_summary: Option<String>,
+    element: Option<Value>,
+}
+
+impl RawViolation {
+    fn into_violation(self, handle: &SessionHandle) -> WebDriverResult<AxeViolation> {
+        let nodes = self
+            .nodes
+            .into_iter()
+            .map(|n| {
+                let element = match n.element {
+                    Some(v) => Some(WebElement::from_json(v, handle.clone())?),
+                    None => None,
+                };
+                Ok(AxeNode {
+                    html: n.html,
+                    target: n.target,
+                    failure_summary: n.failure_summary,
+                    element,
+                })
+            })
+            .collect::<WebDriverResult<Vec<_>>>()?;
+
+        Ok(AxeViolation {
+            id: self.id,
+            impact: self.impact,
+            description: self.description,
+            help: self.help,
+            help_url: self.help_url,
+            tags: self.tags,
+            nodes,
+        })
+    }
+}
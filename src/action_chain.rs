@@ -1,12 +1,177 @@
+use crate::common::command::W3CCommand;
 use crate::session::handle::SessionHandle;
-use crate::{error::WebDriverResult, WebElement};
-use fantoccini::actions::{
-    ActionSequence, InputSource, KeyAction, KeyActions, MouseActions, PointerAction,
-    MOUSE_BUTTON_LEFT, MOUSE_BUTTON_RIGHT,
-};
+use crate::{error::WebDriverResult, Shortcut, ShortcutKey, WebElement};
+use fantoccini::actions::{MOUSE_BUTTON_LEFT, MOUSE_BUTTON_RIGHT};
+use fantoccini::key::Key;
+use serde_json::{json, Value};
 
 use std::time::Duration;
 
+/// The id of the key input source.
+const KEY_SOURCE: usize = 0;
+/// The id of the default (mouse) pointer input source.
+const MOUSE_SOURCE: usize = 1;
+/// The horizontal distance (in pixels) between fingers for multi-finger gestures.
+const FINGER_SPACING: i64 = 40;
+/// The duration of each move when human-like moves are enabled but no move
+/// duration has been set.
+const DEFAULT_HUMAN_MOVE_DURATION: Duration = Duration::from_millis(250);
+
+/// Get the viewport coordinates of the center of an element.
+const ELEMENT_CENTER_SCRIPT: &str = r#"
+    const rect = arguments[0].getBoundingClientRect();
+    return [rect.left + rect.width / 2, rect.top + rect.height / 2];
+"#;
+
+/// The type of device represented by a pointer input source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerType {
+    Mouse,
+    Pen,
+    Touch,
+}
+
+impl PointerType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PointerType::Mouse => "mouse",
+            PointerType::Pen => "pen",
+            PointerType::Touch => "touch",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SourceKind {
+    Key,
+    Pointer(PointerType),
+    Wheel,
+}
+
+#[derive(Debug, Clone)]
+enum MoveOrigin {
+    Viewport,
+    Pointer,
+    Element(WebElement),
+}
+
+impl MoveOrigin {
+    fn to_json(&self) -> WebDriverResult<Value> {
+        let value = match self {
+            MoveOrigin::Viewport => json!("viewport"),
+            MoveOrigin::Pointer => json!("pointer"),
+            MoveOrigin::Element(element) => element.to_json()?,
+        };
+        Ok(value)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum InputAction {
+    Pause(Duration),
+    KeyDown(ShortcutKey),
+    KeyUp(ShortcutKey),
+    PointerDown(u64),
+    PointerUp(u64),
+    PointerMove {
+        origin: MoveOrigin,
+        duration: Option<Duration>,
+        x: i64,
+        y: i64,
+        /// The number of eased steps to split this move into when performed.
+        steps: usize,
+    },
+    Scroll {
+        origin: MoveOrigin,
+        duration: Option<Duration>,
+        x: i64,
+        y: i64,
+        delta_x: i64,
+        delta_y: i64,
+    },
+}
+
+impl InputAction {
+    fn to_json(&self, primary: Key) -> WebDriverResult<Value> {
+        let value = match self {
+            InputAction::Pause(duration) => {
+                json!({"type": "pause", "duration": duration.as_millis() as u64})
+            }
+            InputAction::KeyDown(key) => {
+                json!({"type": "keyDown", "value": key.resolve(primary).to_string()})
+            }
+            InputAction::KeyUp(key) => {
+                json!({"type": "keyUp", "value": key.resolve(primary).to_string()})
+            }
+            InputAction::PointerDown(button) => json!({"type": "pointerDown", "button": button}),
+            InputAction::PointerUp(button) => json!({"type": "pointerUp", "button": button}),
+            InputAction::PointerMove {
+                origin,
+                duration,
+                x,
+                y,
+                ..
+            } => {
+                let origin = origin.to_json()?;
+                let mut value = json!({"type": "pointerMove", "origin": origin, "x": x, "y": y});
+                if let Some(duration) = duration {
+                    value["duration"] = json!(duration.as_millis() as u64);
+                }
+                value
+            }
+            InputAction::Scroll {
+                origin,
+                duration,
+                x,
+                y,
+                delta_x,
+                delta_y,
+            } => {
+                let origin = origin.to_json()?;
+                let mut value = json!({
+                    "type": "scroll",
+                    "origin": origin,
+                    "x": x,
+                    "y": y,
+                    "deltaX": delta_x,
+                    "deltaY": delta_y
+                });
+                if let Some(duration) = duration {
+                    value["duration"] = json!(duration.as_millis() as u64);
+                }
+                value
+            }
+        };
+        Ok(value)
+    }
+}
+
+/// A single input source along with its actions, one per tick.
+#[derive(Debug, Clone)]
+struct InputSequence {
+    id: String,
+    kind: SourceKind,
+    actions: Vec<InputAction>,
+}
+
+impl InputSequence {
+    fn to_json(&self, primary: Key) -> WebDriverResult<Value> {
+        let actions =
+            self.actions.iter().map(|a| a.to_json(primary)).collect::<WebDriverResult<Vec<_>>>()?;
+        let value = match self.kind {
+            SourceKind::Key => json!({"type": "key", "id": self.id, "actions": actions}),
+            SourceKind::Pointer(pointer_type) => json!({
+                "type": "pointer",
+                "id": self.id,
+                "parameters": {"pointerType": pointer_type.as_str()},
+                "actions": actions
+            }),
+            SourceKind::Wheel => json!({"type": "wheel", "id": self.id, "actions": actions}),
+        };
+        Ok(value)
+    }
+}
+
 /// The ActionChain struct allows you to perform multiple input actions in
 /// a sequence, including drag-and-drop, send keystrokes to an element, and
 /// hover the mouse over an element.
@@ -14,14 +179,24 @@ use std::time::Duration;
 /// The easiest way to construct an ActionChain struct is via the WebDriver
 /// struct.
 ///
+/// Every ActionChain starts with a key input source and a mouse pointer.
+/// Touch and pen pointers can be added with [`ActionChain::with_pointer()`],
+/// and the touch gestures such as [`ActionChain::tap()`] and [`ActionChain::pinch()`]
+/// add as many touch pointers as they need. The scroll methods such as
+/// [`ActionChain::scroll_by()`] use a wheel input source. All input sources are kept in sync,
+/// so actions on different pointers can happen at the same time.
+///
 /// # Example:
 /// ```ignore
 /// driver.action_chain().drag_and_drop_element(elem_src, elem_target).perform().await?;
 /// ```
 pub struct ActionChain {
     handle: SessionHandle,
-    key_actions: Option<KeyActions>,
-    mouse_actions: Option<MouseActions>,
+    sources: Vec<InputSequence>,
+    /// The pointer used by the mouse-style methods such as `click()` and `move_to()`.
+    pointer: usize,
+    move_duration: Option<Duration>,
+    move_steps: usize,
 }
 
 impl ActionChain {
@@ -32,79 +207,171 @@ impl ActionChain {
     pub fn new(handle: SessionHandle) -> Self {
         ActionChain {
             handle,
-            key_actions: Some(KeyActions::new("key".to_string())),
-            mouse_actions: Some(MouseActions::new("mouse".to_string())),
+            sources: vec![
+                InputSequence {
+                    id: "key".to_string(),
+                    kind: SourceKind::Key,
+                    actions: Vec::new(),
+                },
+                InputSequence {
+                    id: "mouse".to_string(),
+                    kind: SourceKind::Pointer(PointerType::Mouse),
+                    actions: Vec::new(),
+                },
+            ],
+            pointer: MOUSE_SOURCE,
+            move_duration: None,
+            move_steps: 1,
         }
     }
 
-    /// Add a pause for the key sequence. Usually required after adding a mouse event,
-    /// to keep the key sequence in sync with the mouse sequence.
-    fn add_key_pause(&mut self) {
-        self.key_actions = Some(self.key_actions.take().unwrap().pause(Duration::from_millis(0)));
+    /// Get the index of the input source with the specified id, adding it if needed.
+    fn source_index(&mut self, id: &str, kind: SourceKind) -> usize {
+        if let Some(index) = self.sources.iter().position(|s| s.id == id) {
+            let existing = &self.sources[index].kind;
+            assert!(
+                *existing == kind,
+                "input source '{}' is a {:?} and cannot be used as a {:?}",
+                id,
+                existing,
+                kind
+            );
+            return index;
+        }
+
+        // Pad the new source so that it stays in sync with the existing ones.
+        let ticks = self.sources[KEY_SOURCE].actions.len();
+        self.sources.push(InputSequence {
+            id: id.to_string(),
+            kind,
+            actions: vec![InputAction::Pause(Duration::ZERO); ticks],
+        });
+        self.sources.len() - 1
+    }
+
+    /// Get the index of the touch pointer for the specified finger, starting at 0.
+    fn finger_index(&mut self, finger: usize) -> usize {
+        self.source_index(&format!("finger{}", finger + 1), SourceKind::Pointer(PointerType::Touch))
+    }
+
+    /// Add one tick containing the specified actions. All other input sources
+    /// get a pause, to keep every sequence in sync.
+    fn add_tick(&mut self, actions: Vec<(usize, InputAction)>) {
+        for (index, action) in actions {
+            self.sources[index].actions.push(action);
+        }
+        let ticks = self.sources.iter().map(|s| s.actions.len()).max().unwrap_or_default();
+        for source in &mut self.sources {
+            source.actions.resize(ticks, InputAction::Pause(Duration::ZERO));
+        }
     }
 
     fn add_key_down(&mut self, key: char) {
-        self.key_actions = Some(self.key_actions.take().unwrap().then(KeyAction::Down {
-            value: key,
-        }));
-        self.add_mouse_pause();
+        self.add_tick(vec![(KEY_SOURCE, InputAction::KeyDown(ShortcutKey::Key(key)))]);
     }
 
     fn add_key_up(&mut self, key: char) {
-        self.key_actions = Some(self.key_actions.take().unwrap().then(KeyAction::Up {
-            value: key,
-        }));
-        self.add_mouse_pause();
+        self.add_tick(vec![(KEY_SOURCE, InputAction::KeyUp(ShortcutKey::Key(key)))]);
     }
 
-    /// Add a pause for the mouse sequence. Usually required after adding a key event,
-    /// to keep the mouse sequence in sync with the key sequence.
-    fn add_mouse_pause(&mut self) {
-        self.mouse_actions =
-            Some(self.mouse_actions.take().unwrap().pause(Duration::from_millis(0)));
+    fn add_pointer_down(&mut self, button: u64) {
+        self.add_tick(vec![(self.pointer, InputAction::PointerDown(button))]);
     }
 
-    fn add_mouse_down(&mut self, button: u64) {
-        self.mouse_actions = Some(self.mouse_actions.take().unwrap().then(PointerAction::Down {
-            button,
-        }));
-        self.add_key_pause();
+    fn add_pointer_up(&mut self, button: u64) {
+        self.add_tick(vec![(self.pointer, InputAction::PointerUp(button))]);
     }
 
-    fn add_mouse_up(&mut self, button: u64) {
-        self.mouse_actions = Some(self.mouse_actions.take().unwrap().then(PointerAction::Up {
-            button,
-        }));
-        self.add_key_pause();
+    /// The duration for the next pointer move.
+    fn move_duration(&self) -> Option<Duration> {
+        match self.move_duration {
+            None if self.move_steps > 1 => Some(DEFAULT_HUMAN_MOVE_DURATION),
+            d => d,
+        }
+    }
+
+    fn add_move(&mut self, origin: MoveOrigin, x: i64, y: i64) {
+        let action = InputAction::PointerMove {
+            origin,
+            duration: self.move_duration(),
+            x,
+            y,
+            steps: self.move_steps,
+        };
+        self.add_tick(vec![(self.pointer, action)]);
     }
 
     fn add_move_to_element(&mut self, element: &WebElement, x_offset: i64, y_offset: i64) {
-        self.mouse_actions =
-            Some(self.mouse_actions.take().unwrap().then(PointerAction::MoveToElement {
-                element: element.element.clone(),
-                duration: None,
-                x: x_offset,
-                y: y_offset,
-            }));
-        self.add_key_pause();
+        self.add_move(MoveOrigin::Element(element.clone()), x_offset, y_offset);
     }
 
     fn add_move_to(&mut self, x: i64, y: i64) {
-        self.mouse_actions = Some(self.mouse_actions.take().unwrap().then(PointerAction::MoveTo {
-            duration: None,
-            x,
-            y,
-        }));
-        self.add_key_pause();
+        self.add_move(MoveOrigin::Viewport, x, y);
     }
 
     fn add_move_by(&mut self, x: i64, y: i64) {
-        self.mouse_actions = Some(self.mouse_actions.take().unwrap().then(PointerAction::MoveBy {
+        self.add_move(MoveOrigin::Pointer, x, y);
+    }
+
+    fn add_scroll(&mut self, origin: MoveOrigin, delta_x: i64, delta_y: i64) {
+        let index = self.source_index("wheel", SourceKind::Wheel);
+        let action = InputAction::Scroll {
+            origin,
+            duration: self.move_duration,
+            x: 0,
+            y: 0,
+            delta_x,
+            delta_y,
+        };
+        self.add_tick(vec![(index, action)]);
+    }
+
+    /// Add a touch gesture using one finger per entry in `from`. Each finger is
+    /// placed at its `from` position, pressed down, held for `hold`, moved to its
+    /// `to` position over `duration` (if different), and then lifted.
+    fn add_touch_gesture(
+        &mut self,
+        origin: MoveOrigin,
+        from: &[(i64, i64)],
+        to: &[(i64, i64)],
+        hold: Option<Duration>,
+        duration: Duration,
+    ) {
+        let fingers: Vec<usize> = (0..from.len()).map(|i| self.finger_index(i)).collect();
+        let steps = self.move_steps;
+        let tick = |f: &dyn Fn(usize) -> InputAction| -> Vec<(usize, InputAction)> {
+            fingers.iter().enumerate().map(|(i, index)| (*index, f(i))).collect()
+        };
+
+        let start = tick(&|i| InputAction::PointerMove {
+            origin: origin.clone(),
             duration: None,
-            x,
-            y,
-        }));
-        self.add_key_pause();
+            x: from[i].0,
+            y: from[i].1,
+            steps: 1,
+        });
+        let down = tick(&|_| InputAction::PointerDown(MOUSE_BUTTON_LEFT));
+        let pause = hold.map(|hold| tick(&|_| InputAction::Pause(hold)));
+        let end = (from != to).then(|| {
+            tick(&|i| InputAction::PointerMove {
+                origin: origin.clone(),
+                duration: Some(duration),
+                x: to[i].0,
+                y: to[i].1,
+                steps,
+            })
+        });
+        let up = tick(&|_| InputAction::PointerUp(MOUSE_BUTTON_LEFT));
+
+        self.add_tick(start);
+        self.add_tick(down);
+        if let Some(pause) = pause {
+            self.add_tick(pause);
+        }
+        if let Some(end) = end {
+            self.add_tick(end);
+        }
+        self.add_tick(up);
     }
 
     /// Reset all actions, reverting all input devices back to default states.
@@ -141,16 +408,345 @@ impl ActionChain {
     /// Perform the action sequence. No actions are actually performed until
     /// this method is called.
     pub async fn perform(self) -> WebDriverResult<()> {
+        let sources = self.resolve_eased_moves().await?;
+        let uses_primary = sources.iter().any(|s| {
+            s.actions.iter().any(|a| {
+                matches!(
+                    a,
+                    InputAction::KeyDown(ShortcutKey::Primary)
+                        | InputAction::KeyUp(ShortcutKey::Primary)
+                )
+            })
+        });
+        let primary = if uses_primary {
+            self.handle.primary_modifier().await?
+        } else {
+            Key::Control
+        };
+        let sources =
+            sources.iter().map(|s| s.to_json(primary)).collect::<WebDriverResult<Vec<_>>>()?;
         self.handle
             .client
-            .perform_actions(vec![
-                ActionSequence::from(self.key_actions.unwrap()),
-                ActionSequence::from(self.mouse_actions.unwrap()),
-            ])
+            .issue_cmd(W3CCommand::PerformActions(json!({ "actions": sources })))
             .await?;
+        self.handle.slow_motion().await;
         Ok(())
     }
 
+    /// Expand every move with more than one step into a series of eased moves.
+    ///
+    /// The intermediate positions need absolute coordinates, so the pointer positions
+    /// are tracked through the chain, using the current location of any elements that
+    /// are used as a move origin. A move can only be expanded if the pointer position
+    /// before it is known, so the first move for each pointer is never expanded.
+    ///
+    /// Element locations are read before any actions run, so the intermediate steps of
+    /// a move to an element assume that the element does not move during the chain.
+    /// The final step still moves relative to the element itself.
+    async fn resolve_eased_moves(&self) -> WebDriverResult<Vec<InputSequence>> {
+        let is_eased =
+            |a: &InputAction| matches!(a, InputAction::PointerMove { steps, .. } if *steps > 1);
+        if !self.sources.iter().any(|s| s.actions.iter().any(is_eased)) {
+            return Ok(self.sources.clone());
+        }
+
+        let ticks = self.sources[KEY_SOURCE].actions.len();
+        let mut positions: Vec<Option<(i64, i64)>> = vec![None; self.sources.len()];
+        let mut resolved: Vec<InputSequence> = self
+            .sources
+            .iter()
+            .map(|s| InputSequence {
+                id: s.id.clone(),
+                kind: s.kind.clone(),
+                actions: Vec::new(),
+            })
+            .collect();
+
+        for tick in 0..ticks {
+            let mut expanded: Vec<Vec<InputAction>> = Vec::with_capacity(self.sources.len());
+            for (index, source) in self.sources.iter().enumerate() {
+                let action = &source.actions[tick];
+                let InputAction::PointerMove {
+                    origin,
+                    duration,
+                    x,
+                    y,
+                    steps,
+                } = action
+                else {
+                    expanded.push(vec![action.clone()]);
+                    continue;
+                };
+
+                let start = positions[index];
+                let end = match origin {
+                    MoveOrigin::Viewport => Some((*x, *y)),
+                    MoveOrigin::Pointer => start.map(|(sx, sy)| (sx + x, sy + y)),
+                    MoveOrigin::Element(element) => {
+                        let ret = self
+                            .handle
+                            .execute_script(ELEMENT_CENTER_SCRIPT, vec![element.to_json()?])
+                            .await?;
+                        let (cx, cy): (f64, f64) = ret.convert()?;
+                        Some((cx.round() as i64 + x, cy.round() as i64 + y))
+                    }
+                };
+                positions[index] = end;
+
+                match (start, end) {
+                    (Some(start), Some(end)) if *steps > 1 => {
+                        let mut moves = eased_moves(start, end, *duration, *steps);
+                        // Keep the element as the origin of the final step, in case earlier
+                        // actions (such as scrolling) move the element.
+                        if let (
+                            MoveOrigin::Element(_),
+                            Some(InputAction::PointerMove {
+                                origin: last_origin,
+                                x: last_x,
+                                y: last_y,
+                                ..
+                            }),
+                        ) = (origin, moves.last_mut())
+                        {
+                            *last_origin = origin.clone();
+                            *last_x = *x;
+                            *last_y = *y;
+                        }
+                        expanded.push(moves);
+                    }
+                    _ => expanded.push(vec![action.clone()]),
+                }
+            }
+
+            // Keep the other sources in sync with the expanded moves.
+            let len = expanded.iter().map(|a| a.len()).max().unwrap_or_default();
+            for (source, mut actions) in resolved.iter_mut().zip(expanded) {
+                actions.resize(len, InputAction::Pause(Duration::ZERO));
+                source.actions.extend(actions);
+            }
+        }
+
+        Ok(resolved)
+    }
+
+    /// Add a pause of the specified duration. All input sources wait for the pause
+    /// to finish before continuing.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// // Some drag-and-drop libraries need a moment after the button is pressed.
+    /// driver.action_chain()
+    ///     .click_and_hold_element(&elem_src)
+    ///     .pause(Duration::from_millis(200))
+    ///     .release_on_element(&elem_target)
+    ///     .perform().await?;
+    /// ```
+    pub fn pause(mut self, duration: Duration) -> Self {
+        self.add_tick(vec![(KEY_SOURCE, InputAction::Pause(duration))]);
+        self
+    }
+
+    /// Set the duration of all subsequent pointer moves and scrolls. The pointer
+    /// moves gradually to its destination over this time, rather than jumping there.
+    ///
+    /// This can be called again between moves to give each move its own duration.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// driver.action_chain()
+    ///     .with_move_duration(Duration::from_millis(500))
+    ///     .move_to(200, 200)
+    ///     .with_move_duration(Duration::from_millis(100))
+    ///     .move_by_offset(50, 0)
+    ///     .perform().await?;
+    /// ```
+    pub fn with_move_duration(mut self, duration: Duration) -> Self {
+        self.move_duration = Some(duration);
+        self
+    }
+
+    /// Split all subsequent pointer moves into the specified number of smaller moves,
+    /// following an ease-in-out curve, to mimic a human moving the pointer.
+    /// Pass 1 to turn this off again.
+    ///
+    /// The move duration set by [`ActionChain::with_move_duration()`] is spread across
+    /// the steps. If no move duration was set, each move takes 250ms.
+    ///
+    /// Some drag-and-drop libraries and canvas apps ignore a pointer that jumps
+    /// straight to its destination.
+    ///
+    /// **NOTE:** The first move of each pointer in the chain is not split, since its
+    /// starting position is not known. For moves to an element, the intermediate steps
+    /// are based on where the element is before the chain is performed, so they are off
+    /// if earlier actions (e.g. scrolling) move the element. The final step always ends
+    /// on the element.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// driver.action_chain()
+    ///     .with_human_like_moves(20)
+    ///     .drag_and_drop_element(&elem_src, &elem_target)
+    ///     .perform().await?;
+    /// ```
+    pub fn with_human_like_moves(mut self, steps: usize) -> Self {
+        self.move_steps = steps.max(1);
+        self
+    }
+
+    /// Direct all subsequent pointer actions (`click()`, `move_to()`, `release()` etc.)
+    /// to the pointer with the specified id, adding it if it does not exist yet.
+    ///
+    /// The default pointer has the id `"mouse"` and type [`PointerType::Mouse`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the id is already used by an input source of a different type, e.g.
+    /// `"mouse"` with [`PointerType::Pen`].
+    ///
+    /// # Example:
+    /// ```ignore
+    /// // Draw a line with a pen.
+    /// driver.action_chain()
+    ///     .with_pointer("pen", PointerType::Pen)
+    ///     .move_to(100, 100)
+    ///     .click_and_hold()
+    ///     .move_by_offset(50, 0)
+    ///     .release()
+    ///     .perform().await?;
+    /// ```
+    pub fn with_pointer(mut self, id: impl AsRef<str>, pointer_type: PointerType) -> Self {
+        self.pointer = self.source_index(id.as_ref(), SourceKind::Pointer(pointer_type));
+        self
+    }
+
+    /// Tap with one finger at the specified position, relative to the top-left corner
+    /// of the viewport.
+    pub fn tap(mut self, x: i64, y: i64) -> Self {
+        self.add_touch_gesture(MoveOrigin::Viewport, &[(x, y)], &[(x, y)], None, Duration::ZERO);
+        self
+    }
+
+    /// Tap with one finger on the center of the specified element.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let elem = driver.find_element(By::Id("button1")).await?;
+    /// driver.action_chain().tap_element(&elem).perform().await?;
+    /// ```
+    pub fn tap_element(mut self, element: &WebElement) -> Self {
+        let origin = MoveOrigin::Element(element.clone());
+        self.add_touch_gesture(origin, &[(0, 0)], &[(0, 0)], None, Duration::ZERO);
+        self
+    }
+
+    /// Press one finger at the specified position (relative to the top-left corner
+    /// of the viewport) and hold it there for the specified duration.
+    pub fn long_press(mut self, x: i64, y: i64, duration: Duration) -> Self {
+        self.add_touch_gesture(
+            MoveOrigin::Viewport,
+            &[(x, y)],
+            &[(x, y)],
+            Some(duration),
+            Duration::ZERO,
+        );
+        self
+    }
+
+    /// Press one finger on the center of the specified element and hold it there
+    /// for the specified duration.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let elem = driver.find_element(By::Id("item1")).await?;
+    /// driver.action_chain().long_press_element(&elem, Duration::from_secs(1)).perform().await?;
+    /// ```
+    pub fn long_press_element(mut self, element: &WebElement, duration: Duration) -> Self {
+        let origin = MoveOrigin::Element(element.clone());
+        self.add_touch_gesture(origin, &[(0, 0)], &[(0, 0)], Some(duration), Duration::ZERO);
+        self
+    }
+
+    /// Swipe one finger from one position to another over the specified duration.
+    /// Positions are relative to the top-left corner of the viewport.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// // Swipe up.
+    /// driver.action_chain().swipe((200, 600), (200, 100), Duration::from_millis(300)).perform().await?;
+    /// ```
+    pub fn swipe(self, from: (i64, i64), to: (i64, i64), duration: Duration) -> Self {
+        self.swipe_with_fingers(1, from, to, duration)
+    }
+
+    /// Swipe with the specified number of fingers, side by side, from one position to
+    /// another over the specified duration. The positions give the center of the fingers,
+    /// relative to the top-left corner of the viewport.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// // Two-finger swipe to the left.
+    /// driver.action_chain()
+    ///     .swipe_with_fingers(2, (400, 300), (100, 300), Duration::from_millis(300))
+    ///     .perform().await?;
+    /// ```
+    pub fn swipe_with_fingers(
+        mut self,
+        fingers: usize,
+        from: (i64, i64),
+        to: (i64, i64),
+        duration: Duration,
+    ) -> Self {
+        let offsets: Vec<i64> = (0..fingers)
+            .map(|i| (2 * i as i64 - (fingers as i64 - 1)) * FINGER_SPACING / 2)
+            .collect();
+        let from: Vec<(i64, i64)> = offsets.iter().map(|o| (from.0 + o, from.1)).collect();
+        let to: Vec<(i64, i64)> = offsets.iter().map(|o| (to.0 + o, to.1)).collect();
+        self.add_touch_gesture(MoveOrigin::Viewport, &from, &to, None, duration);
+        self
+    }
+
+    /// Pinch with two fingers around the specified center position (relative to the
+    /// top-left corner of the viewport). The fingers start `start_distance` pixels
+    /// apart and move horizontally until they are `end_distance` pixels apart.
+    ///
+    /// Use an `end_distance` larger than `start_distance` to zoom in, or smaller to
+    /// zoom out.
+    pub fn pinch(
+        mut self,
+        center: (i64, i64),
+        start_distance: i64,
+        end_distance: i64,
+        duration: Duration,
+    ) -> Self {
+        let from = pinch_positions(center, start_distance);
+        let to = pinch_positions(center, end_distance);
+        self.add_touch_gesture(MoveOrigin::Viewport, &from, &to, None, duration);
+        self
+    }
+
+    /// Pinch with two fingers around the center of the specified element.
+    ///
+    /// See [`ActionChain::pinch()`] for more details.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// // Zoom in on a map.
+    /// let map = driver.find_element(By::Id("map")).await?;
+    /// driver.action_chain().pinch_element(&map, 50, 250, Duration::from_millis(500)).perform().await?;
+    /// ```
+    pub fn pinch_element(
+        mut self,
+        element: &WebElement,
+        start_distance: i64,
+        end_distance: i64,
+        duration: Duration,
+    ) -> Self {
+        let from = pinch_positions((0, 0), start_distance);
+        let to = pinch_positions((0, 0), end_distance);
+        self.add_touch_gesture(MoveOrigin::Element(element.clone()), &from, &to, None, duration);
+        self
+    }
+
     /// Click and release the left mouse button.
     ///
     /// # Example:
@@ -173,8 +769,8 @@ impl ActionChain {
     /// # }
     /// ```
     pub fn click(mut self) -> Self {
-        self.add_mouse_down(MOUSE_BUTTON_LEFT);
-        self.add_mouse_up(MOUSE_BUTTON_LEFT);
+        self.add_pointer_down(MOUSE_BUTTON_LEFT);
+        self.add_pointer_up(MOUSE_BUTTON_LEFT);
         self
     }
 
@@ -229,7 +825,7 @@ impl ActionChain {
     /// # }
     /// ```
     pub fn click_and_hold(mut self) -> Self {
-        self.add_mouse_down(MOUSE_BUTTON_LEFT);
+        self.add_pointer_down(MOUSE_BUTTON_LEFT);
         self
     }
 
@@ -285,8 +881,8 @@ impl ActionChain {
     /// # }
     /// ```
     pub fn context_click(mut self) -> Self {
-        self.add_mouse_down(MOUSE_BUTTON_RIGHT);
-        self.add_mouse_up(MOUSE_BUTTON_RIGHT);
+        self.add_pointer_down(MOUSE_BUTTON_RIGHT);
+        self.add_pointer_up(MOUSE_BUTTON_RIGHT);
         self
     }
 
@@ -378,6 +974,9 @@ impl ActionChain {
     /// This method has been confirmed to produce identical JSON output
     /// compared to the python selenium library (which also fails due to
     /// the same bug).
+    ///
+    /// For pages that use the HTML5 drag-and-drop API, use
+    /// [`WebElement::html5_drag_to()`] instead.
     pub fn drag_and_drop_element(self, source: &WebElement, target: &WebElement) -> Self {
         self.click_and_hold_element(source).release_on_element(target)
     }
@@ -697,7 +1296,7 @@ impl ActionChain {
     /// # }
     /// ```
     pub fn release(mut self) -> Self {
-        self.add_mouse_up(MOUSE_BUTTON_LEFT);
+        self.add_pointer_up(MOUSE_BUTTON_LEFT);
         self
     }
 
@@ -764,6 +1363,31 @@ impl ActionChain {
         self
     }
 
+    /// Press the specified keyboard shortcut. All keys are pressed in order and
+    /// then released in reverse order.
+    ///
+    /// If the shortcut uses the primary modifier, it is resolved to Cmd or Ctrl
+    /// (see [`SessionHandle::primary_modifier()`]) when the chain is performed.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let select_all: Shortcut = "Primary+A".parse()?;
+    /// driver.action_chain()
+    ///     .click_element(&editor)
+    ///     .shortcut(&select_all)
+    ///     .send_keys("replacement text")
+    ///     .perform().await?;
+    /// ```
+    pub fn shortcut(mut self, shortcut: &Shortcut) -> Self {
+        for key in shortcut.keys() {
+            self.add_tick(vec![(KEY_SOURCE, InputAction::KeyDown(*key))]);
+        }
+        for key in shortcut.keys().iter().rev() {
+            self.add_tick(vec![(KEY_SOURCE, InputAction::KeyUp(*key))]);
+        }
+        self
+    }
+
     /// Click on the specified element and send the specified keystrokes.
     ///
     /// # Example:
@@ -794,4 +1418,238 @@ impl ActionChain {
     pub fn send_keys_to_element(self, element: &WebElement, text: impl AsRef<str>) -> Self {
         self.click_element(element).send_keys(text)
     }
+
+    /// Scroll the page by the specified amounts using the mouse wheel, with the
+    /// wheel positioned at the top-left corner of the viewport.
+    ///
+    /// Unlike scrolling via JavaScript, this generates real wheel events, so it also
+    /// works for virtualised lists and other content that listens for them.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// // Scroll down by 500 pixels.
+    /// driver.action_chain().scroll_by(0, 500).perform().await?;
+    /// ```
+    pub fn scroll_by(mut self, delta_x: i64, delta_y: i64) -> Self {
+        self.add_scroll(MoveOrigin::Viewport, delta_x, delta_y);
+        self
+    }
+
+    /// Scroll by the specified amounts using the mouse wheel, with the wheel positioned
+    /// over the center of the specified element. Use this to scroll a scrollable
+    /// element rather than the page. If the element is outside the viewport, it is
+    /// scrolled into view first.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let list = driver.find_element(By::Id("list")).await?;
+    /// driver.action_chain().scroll_from_element(&list, 0, 300).perform().await?;
+    /// ```
+    pub fn scroll_from_element(mut self, element: &WebElement, delta_x: i64, delta_y: i64) -> Self {
+        self.add_scroll(MoveOrigin::Element(element.clone()), delta_x, delta_y);
+        self
+    }
+
+    /// Scroll the specified element into view using the mouse wheel.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let footer = driver.find_element(By::Id("footer")).await?;
+    /// driver.action_chain().scroll_to_element(&footer).perform().await?;
+    /// ```
+    pub fn scroll_to_element(self, element: &WebElement) -> Self {
+        self.scroll_from_element(element, 0, 0)
+    }
+}
+
+/// Get the positions of two fingers that are `distance` pixels apart horizontally.
+fn pinch_positions(center: (i64, i64), distance: i64) -> Vec<(i64, i64)> {
+    let (x, y) = center;
+    vec![(x - distance / 2, y), (x + distance / 2, y)]
+}
+
+/// Split a move into the specified number of steps, following an ease-in-out curve.
+fn eased_moves(
+    start: (i64, i64),
+    end: (i64, i64),
+    duration: Option<Duration>,
+    steps: usize,
+) -> Vec<InputAction> {
+    (1..=steps)
+        .map(|step| {
+            // Durations are sent in whole milliseconds, so the last step also gets the
+            // remainder, to keep the total equal to the requested duration.
+            let duration = duration.map(|d| {
+                let total = d.as_millis() as u64;
+                let each = total / steps as u64;
+                if step == steps {
+                    Duration::from_millis(total - each * (steps as u64 - 1))
+                } else {
+                    Duration::from_millis(each)
+                }
+            });
+            let t = step as f64 / steps as f64;
+            let eased = if t < 0.5 {
+                4.0 * t * t * t
+            } else {
+                1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+            };
+            let lerp = |a: i64, b: i64| a + ((b - a) as f64 * eased).round() as i64;
+            InputAction::PointerMove {
+                origin: MoveOrigin::Viewport,
+                duration,
+                x: lerp(start.0, end.0),
+                y: lerp(start.1, end.1),
+                steps: 1,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::fakedriver::FakeWebDriver;
+    use crate::By;
+
+    async fn perform_json(chain: impl FnOnce(ActionChain) -> ActionChain) -> Value {
+        let server = FakeWebDriver::start().await;
+        let driver = server.connect().await;
+        chain(driver.action_chain()).perform().await.unwrap();
+        server.last_body("POST", "actions").unwrap()
+    }
+
+    #[tokio::test]
+    async fn mixed_sources_stay_in_sync() {
+        let actions = perform_json(|chain| {
+            chain.key_down('a').move_to(10, 20).click().key_up('a').scroll_by(0, 100)
+        })
+        .await;
+
+        let pause = json!({"type": "pause", "duration": 0});
+        assert_eq!(
+            actions,
+            json!({"actions": [
+                {"type": "key", "id": "key", "actions": [
+                    {"type": "keyDown", "value": "a"}, pause, pause, pause,
+                    {"type": "keyUp", "value": "a"}, pause
+                ]},
+                {"type": "pointer", "id": "mouse", "parameters": {"pointerType": "mouse"}, "actions": [
+                    pause,
+                    {"type": "pointerMove", "origin": "viewport", "x": 10, "y": 20},
+                    {"type": "pointerDown", "button": 0},
+                    {"type": "pointerUp", "button": 0},
+                    pause, pause
+                ]},
+                {"type": "wheel", "id": "wheel", "actions": [
+                    pause, pause, pause, pause, pause,
+                    {"type": "scroll", "origin": "viewport", "x": 0, "y": 0, "deltaX": 0, "deltaY": 100}
+                ]}
+            ]})
+        );
+    }
+
+    #[tokio::test]
+    async fn pinch_uses_two_touch_pointers() {
+        let actions =
+            perform_json(|chain| chain.pinch((200, 300), 50, 150, Duration::from_millis(400)))
+                .await;
+
+        let pause = json!({"type": "pause", "duration": 0});
+        let finger = |id: &str, from: i64, to: i64| {
+            json!({"type": "pointer", "id": id, "parameters": {"pointerType": "touch"}, "actions": [
+                {"type": "pointerMove", "origin": "viewport", "x": from, "y": 300},
+                {"type": "pointerDown", "button": 0},
+                {"type": "pointerMove", "origin": "viewport", "x": to, "y": 300, "duration": 400},
+                {"type": "pointerUp", "button": 0}
+            ]})
+        };
+        assert_eq!(
+            actions,
+            json!({"actions": [
+                {"type": "key", "id": "key", "actions": [pause, pause, pause, pause]},
+                {"type": "pointer", "id": "mouse", "parameters": {"pointerType": "mouse"},
+                    "actions": [pause, pause, pause, pause]},
+                finger("finger1", 175, 125),
+                finger("finger2", 225, 275)
+            ]})
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "input source 'mouse' is a Pointer(Mouse)")]
+    async fn pointer_id_keeps_its_type() {
+        let server = FakeWebDriver::start().await;
+        let driver = server.connect().await;
+        let _ = driver.action_chain().with_pointer("mouse", PointerType::Pen);
+    }
+
+    #[test]
+    fn eased_moves_end_on_target() {
+        let moves = eased_moves((10, 10), (110, 60), Some(Duration::from_millis(303)), 5);
+        assert_eq!(moves.len(), 5);
+
+        let mut total = Duration::ZERO;
+        let mut last = (0, 0);
+        for action in &moves {
+            let InputAction::PointerMove {
+                duration,
+                x,
+                y,
+                ..
+            } = action
+            else {
+                panic!("unexpected action {:?}", action);
+            };
+            total += duration.unwrap();
+            last = (*x, *y);
+        }
+        assert_eq!(last, (110, 60));
+        assert_eq!(total, Duration::from_millis(303));
+    }
+
+    #[tokio::test]
+    async fn human_like_moves_are_expanded() {
+        let actions =
+            perform_json(|chain| chain.with_human_like_moves(4).move_to(0, 0).move_to(100, 40))
+                .await;
+
+        let mouse = &actions["actions"][1]["actions"];
+        let moves = mouse.as_array().unwrap();
+        // The first move has no known start, so only the second one is split.
+        assert_eq!(moves.len(), 5);
+        assert_eq!(moves[0]["x"], 0);
+        assert_eq!(
+            moves[4],
+            json!({"type": "pointerMove", "origin": "viewport", "x": 100, "y": 40, "duration": 64})
+        );
+        assert_eq!(actions["actions"][0]["actions"].as_array().unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn human_like_moves_end_on_element() {
+        let server = FakeWebDriver::start_with(|req| match req.path.as_str() {
+            "element" => Some((200, json!({"element-6066-11e4-a52e-4f735466cecf": "target"}))),
+            "execute/sync" => Some((200, json!([50.0, 20.0]))),
+            _ => None,
+        })
+        .await;
+        let driver = server.connect().await;
+        let target = driver.find_element(By::Id("target")).await.unwrap();
+        driver
+            .action_chain()
+            .with_human_like_moves(2)
+            .move_to(0, 0)
+            .move_to_element_center(&target)
+            .perform()
+            .await
+            .unwrap();
+
+        let actions = server.last_body("POST", "actions").unwrap();
+        let moves = actions["actions"][1]["actions"].as_array().unwrap();
+        assert_eq!(moves.len(), 3);
+        assert_eq!(moves[1]["origin"], "viewport");
+        assert_eq!(moves[2]["origin"], json!({"element-6066-11e4-a52e-4f735466cecf": "target"}));
+        assert_eq!((&moves[2]["x"], &moves[2]["y"]), (&json!(0), &json!(0)));
+    }
 }
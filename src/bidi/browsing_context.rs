@@ -0,0 +1,203 @@
+//! The BiDi [browsingContext](https://w3c.github.io/webdriver-bidi/#module-browsingContext) module.
+
+use serde::{Deserialize, Serialize};
+
+use super::{BiDiCommand, BiDiEventType, Empty};
+
+/// Get the tree of browsing contexts (tabs, windows and frames).
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTree {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<u32>,
+    /// Only return this context and its descendants.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root: Option<String>,
+}
+
+impl BiDiCommand for GetTree {
+    const METHOD: &'static str = "browsingContext.getTree";
+    type Returns = GetTreeReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GetTreeReturns {
+    pub contexts: Vec<Info>,
+}
+
+/// A browsing context and its children.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Info {
+    pub context: String,
+    pub url: String,
+    #[serde(default)]
+    pub children: Option<Vec<Info>>,
+    pub parent: Option<String>,
+}
+
+/// When `navigate` and `reload` return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReadinessState {
+    None,
+    Interactive,
+    Complete,
+}
+
+/// Navigate the specified browsing context to a URL.
+#[derive(Debug, Clone, Serialize)]
+pub struct Navigate {
+    pub context: String,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait: Option<ReadinessState>,
+}
+
+impl Navigate {
+    /// Navigate and wait for the page to load completely.
+    pub fn new(context: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            context: context.into(),
+            url: url.into(),
+            wait: Some(ReadinessState::Complete),
+        }
+    }
+}
+
+impl BiDiCommand for Navigate {
+    const METHOD: &'static str = "browsingContext.navigate";
+    type Returns = NavigateReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NavigateReturns {
+    pub navigation: Option<String>,
+    pub url: String,
+}
+
+/// Reload the specified browsing context.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reload {
+    pub context: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_cache: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait: Option<ReadinessState>,
+}
+
+impl BiDiCommand for Reload {
+    const METHOD: &'static str = "browsingContext.reload";
+    type Returns = NavigateReturns;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CreateType {
+    Tab,
+    Window,
+}
+
+/// Open a new tab or window.
+#[derive(Debug, Clone, Serialize)]
+pub struct Create {
+    #[serde(rename = "type")]
+    pub create_type: CreateType,
+}
+
+impl BiDiCommand for Create {
+    const METHOD: &'static str = "browsingContext.create";
+    type Returns = CreateReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateReturns {
+    pub context: String,
+}
+
+/// Close the specified top-level browsing context.
+#[derive(Debug, Clone, Serialize)]
+pub struct Close {
+    pub context: String,
+}
+
+impl BiDiCommand for Close {
+    const METHOD: &'static str = "browsingContext.close";
+    type Returns = Empty;
+}
+
+/// Take a screenshot of the specified browsing context.
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureScreenshot {
+    pub context: String,
+}
+
+impl BiDiCommand for CaptureScreenshot {
+    const METHOD: &'static str = "browsingContext.captureScreenshot";
+    type Returns = CaptureScreenshotReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CaptureScreenshotReturns {
+    /// The base64-encoded PNG image.
+    pub data: String,
+}
+
+/// Sent when a browsing context is created (`browsingContext.contextCreated`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContextCreated(pub Info);
+
+impl BiDiEventType for ContextCreated {
+    const METHOD: &'static str = "browsingContext.contextCreated";
+}
+
+/// Sent when a browsing context is closed (`browsingContext.contextDestroyed`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContextDestroyed(pub Info);
+
+impl BiDiEventType for ContextDestroyed {
+    const METHOD: &'static str = "browsingContext.contextDestroyed";
+}
+
+/// The parameters of the navigation events.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NavigationInfo {
+    pub context: String,
+    pub navigation: Option<String>,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    pub url: String,
+}
+
+/// Sent when a navigation starts (`browsingContext.navigationStarted`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct NavigationStarted(pub NavigationInfo);
+
+impl BiDiEventType for NavigationStarted {
+    const METHOD: &'static str = "browsingContext.navigationStarted";
+}
+
+/// Sent when only the URL fragment changed (`browsingContext.fragmentNavigated`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct FragmentNavigated(pub NavigationInfo);
+
+impl BiDiEventType for FragmentNavigated {
+    const METHOD: &'static str = "browsingContext.fragmentNavigated";
+}
+
+/// Sent when the `DOMContentLoaded` event fires (`browsingContext.domContentLoaded`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct DomContentLoaded(pub NavigationInfo);
+
+impl BiDiEventType for DomContentLoaded {
+    const METHOD: &'static str = "browsingContext.domContentLoaded";
+}
+
+/// Sent when the `load` event fires (`browsingContext.load`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct Load(pub NavigationInfo);
+
+impl BiDiEventType for Load {
+    const METHOD: &'static str = "browsingContext.load";
+}
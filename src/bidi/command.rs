@@ -0,0 +1,28 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// A typed WebDriver BiDi command.
+///
+/// The command struct is serialized as the command parameters, and the result is
+/// deserialized into `Returns`.
+///
+/// Use [`BiDiSession::send()`](crate::bidi::BiDiSession::send) to send a command.
+pub trait BiDiCommand: Serialize {
+    /// The BiDi method name, e.g. `session.subscribe`.
+    const METHOD: &'static str;
+    /// The type returned by this command.
+    type Returns: DeserializeOwned;
+}
+
+/// A typed WebDriver BiDi event.
+///
+/// Use [`BiDiSession::events_of()`](crate::bidi::BiDiSession::events_of) to receive
+/// events of this type.
+pub trait BiDiEventType: DeserializeOwned {
+    /// The BiDi event name, e.g. `log.entryAdded`.
+    const METHOD: &'static str;
+}
+
+/// The result of a BiDi command that does not return anything.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Empty {}
@@ -0,0 +1,215 @@
+use futures::channel::mpsc;
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::bidi::session::{Subscribe, Unsubscribe};
+use crate::bidi::{BiDiCommand, BiDiEventType};
+use crate::common::websocket::WebSocketConnection;
+use crate::error::{WebDriverError, WebDriverResult};
+use crate::session::handle::SessionHandle;
+
+/// An event received over a BiDi connection, such as `log.entryAdded`.
+#[derive(Debug, Clone)]
+pub struct BiDiEvent {
+    /// The event name, e.g. `browsingContext.load`.
+    pub method: String,
+    pub params: Value,
+}
+
+impl BiDiEvent {
+    /// Deserialize the event parameters into the specified type.
+    pub fn params_as<T: DeserializeOwned>(&self) -> WebDriverResult<T> {
+        Ok(serde_json::from_value(self.params.clone())?)
+    }
+
+    /// Deserialize this event into the specified event type, or return `None` if it is
+    /// a different event.
+    pub fn parse<E: BiDiEventType>(&self) -> Option<WebDriverResult<E>> {
+        (self.method == E::METHOD).then(|| self.params_as())
+    }
+}
+
+/// A stream of BiDi events. The stream ends when the connection is closed.
+///
+/// See [`BiDiSession::events()`].
+pub struct BiDiEventStream {
+    receiver: mpsc::UnboundedReceiver<Value>,
+}
+
+impl Stream for BiDiEventStream {
+    type Item = BiDiEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let message = match self.receiver.poll_next_unpin(cx) {
+                Poll::Ready(Some(message)) => message,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+            if let Ok(event) = serde_json::from_value::<RawEvent>(message) {
+                return Poll::Ready(Some(BiDiEvent {
+                    method: event.method,
+                    params: event.params,
+                }));
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RawEvent {
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawError {
+    error: String,
+    message: String,
+}
+
+/// A WebDriver BiDi connection to the browser.
+///
+/// Commands and responses are matched by id, so several commands can be in flight at
+/// the same time. Clones share the same websocket. The connection is closed when the
+/// last clone is dropped.
+///
+/// # Example:
+/// ```ignore
+/// use futures::StreamExt;
+/// use thirtyfour::bidi::{browsing_context, script};
+///
+/// let mut caps = DesiredCapabilities::chrome();
+/// caps.set_web_socket_url(true)?;
+/// let driver = WebDriver::new("http://localhost:4444", caps).await?;
+///
+/// let bidi = driver.bidi().await?;
+/// let tree = bidi.send(&browsing_context::GetTree::default()).await?;
+/// let context = tree.contexts[0].context.clone();
+/// let result = bidi.send(&script::Evaluate::new("1 + 2", &context)).await?;
+/// ```
+#[derive(Clone)]
+pub struct BiDiSession {
+    connection: WebSocketConnection,
+}
+
+impl BiDiSession {
+    /// Connect to the specified BiDi websocket URL, as returned in the `webSocketUrl`
+    /// capability of a new session.
+    pub async fn connect(url: &str) -> WebDriverResult<Self> {
+        Ok(Self {
+            connection: WebSocketConnection::connect(url).await?,
+        })
+    }
+
+    /// Connect to the BiDi websocket of the specified session.
+    ///
+    /// This uses the `webSocketUrl` capability returned when the session was created,
+    /// or the URL set with `WebDriverConfig::set_websocket_url()`.
+    pub(crate) async fn connect_to_session(handle: &SessionHandle) -> WebDriverResult<Self> {
+        if let Some(url) = handle.config.get_websocket_url() {
+            return Self::connect(&url).await;
+        }
+
+        let requested = handle.config.get_capabilities();
+        let message = if requested.get("webSocketUrl") == Some(&json!(true)) {
+            "the driver did not return a webSocketUrl for this session; \
+             set it with WebDriverConfig::set_websocket_url()"
+        } else {
+            "BiDi requires the session to be created with the webSocketUrl capability"
+        };
+        Err(WebDriverError::CustomError(message.to_string()))
+    }
+
+    /// Send the specified command with the specified parameters.
+    ///
+    /// If the command fails, a `WebDriverError::BiDiError` is returned.
+    pub async fn send_raw(&self, method: &str, params: Value) -> WebDriverResult<Value> {
+        let mut response =
+            self.connection.send(json!({"method": method, "params": params})).await?;
+        if response["type"] == "error" {
+            let error: RawError = serde_json::from_value(response)?;
+            return Err(WebDriverError::BiDiError {
+                error: error.error,
+                message: error.message,
+            });
+        }
+        Ok(match response["result"].take() {
+            Value::Null => json!({}),
+            v => v,
+        })
+    }
+
+    /// Send the specified typed BiDi command.
+    ///
+    /// See the submodules of the [bidi](crate::bidi) module for the available commands.
+    pub async fn send<C: BiDiCommand>(&self, command: &C) -> WebDriverResult<C::Returns> {
+        let params = serde_json::to_value(command)?;
+        let v = self.send_raw(C::METHOD, params).await?;
+        Ok(serde_json::from_value(v)?)
+    }
+
+    /// Subscribe to the specified events or modules, e.g. `log.entryAdded` or `network`.
+    pub async fn subscribe(&self, events: &[&str]) -> WebDriverResult<()> {
+        self.send(&Subscribe::new(events.iter().copied())).await?;
+        Ok(())
+    }
+
+    /// Unsubscribe from the specified events or modules.
+    pub async fn unsubscribe(&self, events: &[&str]) -> WebDriverResult<()> {
+        self.send(&Unsubscribe::new(events.iter().copied())).await?;
+        Ok(())
+    }
+
+    /// Receive all events from now on. Use [`BiDiSession::subscribe()`] to choose
+    /// which events the browser sends.
+    pub fn events(&self) -> BiDiEventStream {
+        BiDiEventStream {
+            receiver: self.connection.events(),
+        }
+    }
+
+    /// Receive all events of the specified type from now on. Events that cannot be
+    /// deserialized are skipped.
+    pub fn events_of<E: BiDiEventType + Send + 'static>(
+        &self,
+    ) -> impl Stream<Item = E> + Unpin + Send {
+        self.events().filter_map(|event| {
+            let parsed = match event.parse::<E>() {
+                Some(Ok(e)) => Some(e),
+                Some(Err(e)) => {
+                    log::warn!("unable to parse {} event: {}", E::METHOD, e);
+                    None
+                }
+                None => None,
+            };
+            futures::future::ready(parsed)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::fakedriver::{FakeWebDriver, WEBSOCKET_URL};
+    use crate::{CapabilitiesHelper, DesiredCapabilities};
+
+    #[tokio::test]
+    async fn websocket_url_from_new_session() {
+        let server = FakeWebDriver::start().await;
+        let driver = server.connect().await;
+        assert_eq!(driver.config().get_websocket_url(), None);
+        assert!(driver.bidi().await.is_err());
+
+        let mut caps = DesiredCapabilities::chrome();
+        caps.set_web_socket_url(true).unwrap();
+        let driver = server.connect_with(caps).await;
+        assert_eq!(driver.config().get_websocket_url().as_deref(), Some(WEBSOCKET_URL));
+        // The URL comes from the new session response, not from GET /session/{id}.
+        assert!(!server.requests().iter().any(|r| r.method == "GET" && r.path.is_empty()));
+    }
+}
@@ -0,0 +1,46 @@
+//! The BiDi [log](https://w3c.github.io/webdriver-bidi/#module-log) module.
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::script::RemoteValue;
+use super::BiDiEventType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+/// The realm and browsing context a log entry came from.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Source {
+    pub realm: String,
+    pub context: Option<String>,
+}
+
+/// A console message or uncaught JavaScript error (`log.entryAdded`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryAdded {
+    /// The entry type, e.g. `console` or `javascript`.
+    #[serde(rename = "type")]
+    pub entry_type: String,
+    pub level: Level,
+    pub source: Source,
+    pub text: Option<String>,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    pub stack_trace: Option<Value>,
+    /// The console method, e.g. `log` or `error`. Only set for console entries.
+    pub method: Option<String>,
+    /// The console arguments. Only set for console entries.
+    pub args: Option<Vec<RemoteValue>>,
+}
+
+impl BiDiEventType for EntryAdded {
+    const METHOD: &'static str = "log.entryAdded";
+}
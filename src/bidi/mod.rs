@@ -0,0 +1,40 @@
+//! Support for the [WebDriver BiDi](https://w3c.github.io/webdriver-bidi/) protocol.
+//!
+//! WebDriver BiDi is a bidirectional protocol over a websocket. Unlike classic WebDriver,
+//! the browser can send events, such as console messages and network requests, as they
+//! happen. It is supported by recent versions of Chrome, Firefox and Selenium Grid.
+//!
+//! To use BiDi, request the `webSocketUrl` capability when creating the session, and then
+//! call `WebDriver::bidi()` to connect.
+//!
+//! ```ignore
+//! use futures::StreamExt;
+//! use thirtyfour::bidi::log;
+//!
+//! let mut caps = DesiredCapabilities::chrome();
+//! caps.set_web_socket_url(true)?;
+//! let driver = WebDriver::new("http://localhost:4444", caps).await?;
+//!
+//! let bidi = driver.bidi().await?;
+//! let mut entries = bidi.events_of::<log::EntryAdded>();
+//! bidi.subscribe(&["log.entryAdded"]).await?;
+//!
+//! driver.execute_script(r#"console.error("oops");"#, Vec::new()).await?;
+//! let entry = entries.next().await.unwrap();
+//! assert_eq!(entry.level, log::Level::Error);
+//! ```
+//!
+//! Commands are grouped into one submodule per BiDi module, and can be sent with
+//! [`BiDiSession::send()`]. Any other command can be sent with [`BiDiSession::send_raw()`].
+
+mod command;
+mod connection;
+
+pub mod browsing_context;
+pub mod log;
+pub mod network;
+pub mod script;
+pub mod session;
+
+pub use command::{BiDiCommand, BiDiEventType, Empty};
+pub use connection::{BiDiEvent, BiDiEventStream, BiDiSession};
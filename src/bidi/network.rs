@@ -0,0 +1,301 @@
+//! The BiDi [network](https://w3c.github.io/webdriver-bidi/#module-network) module.
+
+use serde::{Deserialize, Serialize};
+
+use super::{BiDiCommand, BiDiEventType, Empty};
+
+/// When an intercepted request is paused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InterceptPhase {
+    BeforeRequestSent,
+    ResponseStarted,
+    AuthRequired,
+}
+
+/// A URL pattern to intercept.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum UrlPattern {
+    /// Match this exact URL.
+    String {
+        pattern: String,
+    },
+    /// Match URLs by their components. Components that are `None` match anything.
+    Pattern {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        protocol: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        hostname: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        port: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pathname: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        search: Option<String>,
+    },
+}
+
+/// A header or body value, which may be binary.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum BytesValue {
+    String(String),
+    /// Base64-encoded bytes.
+    Base64(String),
+}
+
+impl BytesValue {
+    /// Get the value as text. Binary values are decoded as (lossy) UTF-8.
+    pub fn to_text(&self) -> String {
+        match self {
+            BytesValue::String(s) => s.clone(),
+            BytesValue::Base64(b) => match base64::decode(b) {
+                Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+                Err(_) => String::new(),
+            },
+        }
+    }
+}
+
+impl From<&str> for BytesValue {
+    fn from(value: &str) -> Self {
+        BytesValue::String(value.to_string())
+    }
+}
+
+impl From<String> for BytesValue {
+    fn from(value: String) -> Self {
+        BytesValue::String(value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Header {
+    pub name: String,
+    pub value: BytesValue,
+}
+
+impl Header {
+    pub fn new(name: impl Into<String>, value: impl Into<BytesValue>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// Pause matching requests at the specified phases until they are continued,
+/// fulfilled or failed with one of the other commands in this module.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddIntercept {
+    pub phases: Vec<InterceptPhase>,
+    /// Only intercept requests matching any of these patterns. All requests are
+    /// intercepted if this is `None`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url_patterns: Option<Vec<UrlPattern>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contexts: Option<Vec<String>>,
+}
+
+impl BiDiCommand for AddIntercept {
+    const METHOD: &'static str = "network.addIntercept";
+    type Returns = AddInterceptReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AddInterceptReturns {
+    /// The intercept id, used with `RemoveIntercept`.
+    pub intercept: String,
+}
+
+/// Remove an intercept added with `AddIntercept`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoveIntercept {
+    pub intercept: String,
+}
+
+impl BiDiCommand for RemoveIntercept {
+    const METHOD: &'static str = "network.removeIntercept";
+    type Returns = Empty;
+}
+
+/// Continue a paused request, optionally modifying it.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ContinueRequest {
+    /// The request id.
+    pub request: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<BytesValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Vec<Header>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl BiDiCommand for ContinueRequest {
+    const METHOD: &'static str = "network.continueRequest";
+    type Returns = Empty;
+}
+
+/// Continue a response that was paused in the `ResponseStarted` phase.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContinueResponse {
+    pub request: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Vec<Header>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason_phrase: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+}
+
+impl BiDiCommand for ContinueResponse {
+    const METHOD: &'static str = "network.continueResponse";
+    type Returns = Empty;
+}
+
+/// Fail a paused request with a network error.
+#[derive(Debug, Clone, Serialize)]
+pub struct FailRequest {
+    pub request: String,
+}
+
+impl BiDiCommand for FailRequest {
+    const METHOD: &'static str = "network.failRequest";
+    type Returns = Empty;
+}
+
+/// Fulfill a paused request with the specified response, without sending it to the server.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvideResponse {
+    pub request: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<BytesValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Vec<Header>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason_phrase: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+}
+
+impl BiDiCommand for ProvideResponse {
+    const METHOD: &'static str = "network.provideResponse";
+    type Returns = Empty;
+}
+
+/// Request timings, in milliseconds relative to `time_origin`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FetchTimingInfo {
+    pub time_origin: f64,
+    pub request_time: f64,
+    pub redirect_start: f64,
+    pub redirect_end: f64,
+    pub fetch_start: f64,
+    pub dns_start: f64,
+    pub dns_end: f64,
+    pub connect_start: f64,
+    pub connect_end: f64,
+    pub tls_start: f64,
+    pub request_start: f64,
+    pub response_start: f64,
+    pub response_end: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestData {
+    /// The request id.
+    pub request: String,
+    pub url: String,
+    pub method: String,
+    #[serde(default)]
+    pub headers: Vec<Header>,
+    pub headers_size: Option<u64>,
+    pub body_size: Option<u64>,
+    #[serde(default)]
+    pub timings: FetchTimingInfo,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseData {
+    pub url: String,
+    pub protocol: String,
+    pub status: u16,
+    pub status_text: String,
+    pub from_cache: bool,
+    #[serde(default)]
+    pub headers: Vec<Header>,
+    pub mime_type: String,
+    pub bytes_received: u64,
+    pub headers_size: Option<u64>,
+    pub body_size: Option<u64>,
+}
+
+/// The parameters shared by all network events.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestEvent {
+    pub context: Option<String>,
+    /// Whether the request is paused by an intercept.
+    pub is_blocked: bool,
+    pub navigation: Option<String>,
+    pub redirect_count: u64,
+    pub request: RequestData,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The intercepts that paused this request.
+    pub intercepts: Option<Vec<String>>,
+    /// Only set for response events.
+    pub response: Option<ResponseData>,
+    /// Only set for `FetchError`.
+    pub error_text: Option<String>,
+}
+
+/// Sent before a request is sent (`network.beforeRequestSent`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct BeforeRequestSent(pub RequestEvent);
+
+impl BiDiEventType for BeforeRequestSent {
+    const METHOD: &'static str = "network.beforeRequestSent";
+}
+
+/// Sent when the response headers are received (`network.responseStarted`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResponseStarted(pub RequestEvent);
+
+impl BiDiEventType for ResponseStarted {
+    const METHOD: &'static str = "network.responseStarted";
+}
+
+/// Sent when the response body is received (`network.responseCompleted`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResponseCompleted(pub RequestEvent);
+
+impl BiDiEventType for ResponseCompleted {
+    const METHOD: &'static str = "network.responseCompleted";
+}
+
+/// Sent when a request fails (`network.fetchError`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct FetchError(pub RequestEvent);
+
+impl BiDiEventType for FetchError {
+    const METHOD: &'static str = "network.fetchError";
+}
+
+/// Sent when a request requires authentication (`network.authRequired`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuthRequired(pub RequestEvent);
+
+impl BiDiEventType for AuthRequired {
+    const METHOD: &'static str = "network.authRequired";
+}
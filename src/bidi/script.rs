@@ -0,0 +1,197 @@
+//! The BiDi [script](https://w3c.github.io/webdriver-bidi/#module-script) module.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::BiDiCommand;
+
+/// Where a script is run: a browsing context or a specific realm.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum Target {
+    Context {
+        context: String,
+        /// Run in an isolated sandbox with this name, rather than the page's own realm.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        sandbox: Option<String>,
+    },
+    Realm {
+        realm: String,
+    },
+}
+
+impl Target {
+    /// Target the specified browsing context.
+    pub fn context(context: impl Into<String>) -> Self {
+        Target::Context {
+            context: context.into(),
+            sandbox: None,
+        }
+    }
+}
+
+/// Whether the remote end keeps a handle to the returned object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultOwnership {
+    Root,
+    None,
+}
+
+/// Evaluate the specified expression.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Evaluate {
+    pub expression: String,
+    pub target: Target,
+    /// Wait for the result if the expression returns a promise.
+    pub await_promise: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result_ownership: Option<ResultOwnership>,
+}
+
+impl Evaluate {
+    /// Evaluate the expression in the specified browsing context, awaiting any promise.
+    pub fn new(expression: impl Into<String>, context: impl Into<String>) -> Self {
+        Self {
+            expression: expression.into(),
+            target: Target::context(context),
+            await_promise: true,
+            result_ownership: None,
+        }
+    }
+}
+
+impl BiDiCommand for Evaluate {
+    const METHOD: &'static str = "script.evaluate";
+    type Returns = EvaluateResult;
+}
+
+/// Call the specified function declaration, e.g. `(a, b) => a + b`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFunction {
+    pub function_declaration: String,
+    pub target: Target,
+    pub await_promise: bool,
+    /// The arguments, as serialized BiDi local values, e.g. `{"type": "number", "value": 1}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result_ownership: Option<ResultOwnership>,
+}
+
+impl CallFunction {
+    /// Call the function in the specified browsing context, awaiting any promise.
+    pub fn new(function_declaration: impl Into<String>, context: impl Into<String>) -> Self {
+        Self {
+            function_declaration: function_declaration.into(),
+            target: Target::context(context),
+            await_promise: true,
+            arguments: None,
+            result_ownership: None,
+        }
+    }
+}
+
+impl BiDiCommand for CallFunction {
+    const METHOD: &'static str = "script.callFunction";
+    type Returns = EvaluateResult;
+}
+
+/// The result of `script.evaluate` or `script.callFunction`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum EvaluateResult {
+    Success {
+        result: RemoteValue,
+        realm: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Exception {
+        exception_details: ExceptionDetails,
+        realm: String,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExceptionDetails {
+    pub column_number: i64,
+    pub line_number: i64,
+    pub exception: RemoteValue,
+    pub text: String,
+}
+
+/// A value returned from the browser.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteValue {
+    /// The value type, e.g. `string`, `number`, `object` or `node`.
+    #[serde(rename = "type")]
+    pub value_type: String,
+    #[serde(default)]
+    pub value: Option<Value>,
+    pub handle: Option<String>,
+    /// The id of a DOM node, which can be used to refer to it in later commands.
+    pub shared_id: Option<String>,
+}
+
+impl RemoteValue {
+    /// Convert this value to plain JSON, where possible.
+    ///
+    /// Nodes, functions and other values that have no JSON representation become `null`.
+    pub fn to_json(&self) -> Value {
+        let value = self.value.clone().unwrap_or(Value::Null);
+        match self.value_type.as_str() {
+            "string" | "boolean" => value,
+            "number" => match value {
+                // NaN, -0, Infinity and -Infinity are sent as strings.
+                Value::String(_) => Value::Null,
+                v => v,
+            },
+            "array" | "set" => match serde_json::from_value::<Vec<RemoteValue>>(value) {
+                Ok(items) => Value::Array(items.iter().map(RemoteValue::to_json).collect()),
+                Err(_) => Value::Null,
+            },
+            "object" | "map" => match serde_json::from_value::<Vec<(Value, RemoteValue)>>(value) {
+                Ok(entries) => Value::Object(
+                    entries
+                        .into_iter()
+                        .map(|(k, v)| {
+                            let key = match k {
+                                Value::String(s) => s,
+                                k => serde_json::from_value::<RemoteValue>(k)
+                                    .map(|k| k.to_json().to_string())
+                                    .unwrap_or_default(),
+                            };
+                            (key, v.to_json())
+                        })
+                        .collect::<Map<_, _>>(),
+                ),
+                Err(_) => Value::Null,
+            },
+            _ => Value::Null,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn remote_value_to_json() {
+        let value: RemoteValue = serde_json::from_value(json!({
+            "type": "object",
+            "value": [
+                ["name", {"type": "string", "value": "thirtyfour"}],
+                ["tags", {"type": "array", "value": [{"type": "number", "value": 1}, {"type": "null"}]}],
+                ["body", {"type": "node", "sharedId": "abc"}]
+            ]
+        }))
+        .unwrap();
+        assert_eq!(value.to_json(), json!({"name": "thirtyfour", "tags": [1, null], "body": null}));
+    }
+}
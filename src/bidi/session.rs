@@ -0,0 +1,68 @@
+//! The BiDi [session](https://w3c.github.io/webdriver-bidi/#module-session) module.
+
+use serde::{Deserialize, Serialize};
+
+use super::{BiDiCommand, Empty};
+
+/// Get the status of the remote end.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Status {}
+
+impl BiDiCommand for Status {
+    const METHOD: &'static str = "session.status";
+    type Returns = StatusReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StatusReturns {
+    /// Whether the remote end is able to create new sessions.
+    pub ready: bool,
+    pub message: String,
+}
+
+/// Subscribe to the specified events or modules (e.g. `log` or `network.beforeRequestSent`).
+///
+/// Events are only sent to the client once subscribed.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Subscribe {
+    pub events: Vec<String>,
+    /// Only receive events for these browsing contexts (and their descendants).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contexts: Option<Vec<String>>,
+}
+
+impl Subscribe {
+    pub fn new<S: Into<String>>(events: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            events: events.into_iter().map(Into::into).collect(),
+            contexts: None,
+        }
+    }
+}
+
+impl BiDiCommand for Subscribe {
+    const METHOD: &'static str = "session.subscribe";
+    type Returns = Empty;
+}
+
+/// Unsubscribe from the specified events or modules.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Unsubscribe {
+    pub events: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contexts: Option<Vec<String>>,
+}
+
+impl Unsubscribe {
+    pub fn new<S: Into<String>>(events: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            events: events.into_iter().map(Into::into).collect(),
+            contexts: None,
+        }
+    }
+}
+
+impl BiDiCommand for Unsubscribe {
+    const METHOD: &'static str = "session.unsubscribe";
+    type Returns = Empty;
+}
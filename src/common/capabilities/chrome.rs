@@ -169,6 +169,85 @@ impl ChromeCapabilities {
     pub fn unset_ignore_certificate_errors(&mut self) -> WebDriverResult<()> {
         self.remove_chrome_arg("--ignore-certificate-errors")
     }
+
+    /// Emulate a mobile device, either by name or by its screen metrics and user agent.
+    pub fn set_mobile_emulation(
+        &mut self,
+        mobile_emulation: MobileEmulation,
+    ) -> WebDriverResult<()> {
+        self.add_chrome_option("mobileEmulation", mobile_emulation)
+    }
+
+    /// Unset mobile emulation.
+    pub fn unset_mobile_emulation(&mut self) -> WebDriverResult<()> {
+        self.remove_subkey("goog:chromeOptions", "mobileEmulation")
+    }
+
+    /// Set the user profile preferences to use.
+    pub fn set_preferences(&mut self, preferences: ChromePreferences) -> WebDriverResult<()> {
+        self.add_chrome_option("prefs", preferences)
+    }
+
+    /// Set the preferences for the browser's Local State file, which are shared by all
+    /// profiles, e.g. `browser.enabled_labs_experiments`.
+    pub fn set_local_state(&mut self, local_state: ChromePreferences) -> WebDriverResult<()> {
+        self.add_chrome_option("localState", local_state)
+    }
+
+    /// Get the current list of command-line switches that chromedriver should not pass to
+    /// Chrome by default.
+    pub fn get_exclude_switches(&self) -> Vec<String> {
+        self.get_chrome_option("excludeSwitches")
+    }
+
+    /// Stop chromedriver from passing the specified default command-line switch to Chrome,
+    /// e.g. `enable-automation`. The switch is specified without the leading `--`.
+    pub fn add_exclude_switch(&mut self, switch: &str) -> WebDriverResult<()> {
+        let mut switches = self.get_exclude_switches();
+        let switch_string = switch.trim_start_matches("--").to_string();
+        if !switches.contains(&switch_string) {
+            switches.push(switch_string);
+        }
+        self.add_chrome_option("excludeSwitches", to_value(switches)?)
+    }
+
+    /// Remove the specified switch from the excluded switches if it had been added previously.
+    pub fn remove_exclude_switch(&mut self, switch: &str) -> WebDriverResult<()> {
+        let mut switches = self.get_exclude_switches();
+        if switches.is_empty() {
+            Ok(())
+        } else {
+            switches.retain(|v| v != switch.trim_start_matches("--"));
+            self.add_chrome_option("excludeSwitches", to_value(switches)?)
+        }
+    }
+
+    /// Set the performance logging preferences. Performance logging must also be enabled
+    /// by setting the `goog:loggingPrefs` capability to `{"performance": "ALL"}`.
+    pub fn set_perf_logging_prefs(&mut self, prefs: PerfLoggingPrefs) -> WebDriverResult<()> {
+        self.add_chrome_option("perfLoggingPrefs", prefs)
+    }
+
+    /// Get the current list of window types that appear in the list of window handles.
+    pub fn get_window_types(&self) -> Vec<String> {
+        self.get_chrome_option("windowTypes")
+    }
+
+    /// Include windows of the specified type in the list of window handles, e.g. `webview`.
+    pub fn add_window_type(&mut self, window_type: &str) -> WebDriverResult<()> {
+        let mut window_types = self.get_window_types();
+        let window_type_string = window_type.to_string();
+        if !window_types.contains(&window_type_string) {
+            window_types.push(window_type_string);
+        }
+        self.add_chrome_option("windowTypes", to_value(window_types)?)
+    }
+
+    /// Set whether the browser should be left running when chromedriver quits.
+    /// The browser is still closed when the session is quit.
+    pub fn set_detach(&mut self, detach: bool) -> WebDriverResult<()> {
+        self.add_chrome_option("detach", detach)
+    }
 }
 
 impl CapabilitiesHelper for ChromeCapabilities {
@@ -185,8 +264,237 @@ impl CapabilitiesHelper for ChromeCapabilities {
     }
 }
 
+impl From<Capabilities> for ChromeCapabilities {
+    fn from(capabilities: Capabilities) -> ChromeCapabilities {
+        ChromeCapabilities {
+            capabilities,
+        }
+    }
+}
+
 impl From<ChromeCapabilities> for Capabilities {
     fn from(caps: ChromeCapabilities) -> Capabilities {
         caps.capabilities
     }
 }
+
+/// The device to emulate with `ChromeCapabilities::set_mobile_emulation()`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum MobileEmulation {
+    /// A device from the list in Chrome DevTools, e.g. `Pixel 5`.
+    DeviceName {
+        #[serde(rename = "deviceName")]
+        device_name: String,
+    },
+    /// A device with the specified screen metrics and, optionally, user agent.
+    Metrics {
+        #[serde(rename = "deviceMetrics")]
+        device_metrics: MobileDeviceMetrics,
+        #[serde(rename = "userAgent", skip_serializing_if = "Option::is_none")]
+        user_agent: Option<String>,
+    },
+    /// Only the user agent, keeping the default screen metrics.
+    UserAgent {
+        #[serde(rename = "userAgent")]
+        user_agent: String,
+    },
+}
+
+impl MobileEmulation {
+    /// Emulate the device with the specified name from the list in Chrome DevTools.
+    pub fn device_name(device_name: &str) -> Self {
+        MobileEmulation::DeviceName {
+            device_name: device_name.to_string(),
+        }
+    }
+
+    /// Emulate a device with the specified screen metrics and, optionally, user agent.
+    pub fn metrics(device_metrics: MobileDeviceMetrics, user_agent: Option<&str>) -> Self {
+        MobileEmulation::Metrics {
+            device_metrics,
+            user_agent: user_agent.map(String::from),
+        }
+    }
+
+    /// Emulate the specified user agent only.
+    pub fn user_agent(user_agent: &str) -> Self {
+        MobileEmulation::UserAgent {
+            user_agent: user_agent.to_string(),
+        }
+    }
+}
+
+/// The screen metrics of an emulated mobile device.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MobileDeviceMetrics {
+    /// The viewport width, in CSS pixels.
+    pub width: u32,
+    /// The viewport height, in CSS pixels.
+    pub height: u32,
+    /// The device pixel ratio (`window.devicePixelRatio`).
+    pub pixel_ratio: f64,
+    /// Whether to emulate touch events.
+    pub touch: bool,
+    /// Whether to emulate a mobile device, e.g. for the meta viewport tag and scrollbars.
+    pub mobile: bool,
+}
+
+impl MobileDeviceMetrics {
+    /// Create metrics for a mobile device with touch support.
+    pub fn new(width: u32, height: u32, pixel_ratio: f64) -> Self {
+        MobileDeviceMetrics {
+            width,
+            height,
+            pixel_ratio,
+            touch: true,
+            mobile: true,
+        }
+    }
+}
+
+/// Chrome preferences, for use with `ChromeCapabilities::set_preferences()` and
+/// `ChromeCapabilities::set_local_state()`.
+///
+/// Keys use the dotted names from Chrome's `Preferences` file, e.g.
+/// `download.default_directory`.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(transparent)]
+pub struct ChromePreferences {
+    preferences: serde_json::Map<String, Value>,
+}
+
+impl ChromePreferences {
+    pub fn new() -> Self {
+        ChromePreferences::default()
+    }
+
+    /// Sets the specified Chrome preference. This is a helper method for the various
+    /// specific preference methods.
+    pub fn set<T>(&mut self, key: &str, value: T) -> WebDriverResult<()>
+    where
+        T: Serialize,
+    {
+        self.preferences.insert(key.into(), to_value(value)?);
+        Ok(())
+    }
+
+    /// Unsets the specified Chrome preference.
+    pub fn unset(&mut self, key: &str) -> WebDriverResult<()> {
+        self.preferences.remove(key);
+        Ok(())
+    }
+
+    /// Sets the directory that downloads are saved to.
+    pub fn set_download_directory(&mut self, path: &Path) -> WebDriverResult<()> {
+        self.set("download.default_directory", path)?;
+        self.set("download.directory_upgrade", true)
+    }
+
+    /// Sets whether to ask where to save each file before downloading.
+    pub fn set_prompt_for_download(&mut self, value: bool) -> WebDriverResult<()> {
+        self.set("download.prompt_for_download", value)
+    }
+
+    /// Sets whether PDFs are downloaded instead of opened in the built-in viewer.
+    pub fn set_always_open_pdf_externally(&mut self, value: bool) -> WebDriverResult<()> {
+        self.set("plugins.always_open_pdf_externally", value)
+    }
+
+    /// Sets whether Safe Browsing is enabled. When enabled, some downloads are blocked.
+    pub fn set_safe_browsing_enabled(&mut self, value: bool) -> WebDriverResult<()> {
+        self.set("safebrowsing.enabled", value)
+    }
+
+    /// Sets whether the browser offers to save passwords.
+    pub fn set_password_manager_enabled(&mut self, value: bool) -> WebDriverResult<()> {
+        self.set("credentials_enable_service", value)?;
+        self.set("profile.password_manager_enabled", value)
+    }
+
+    /// Sets whether sites may show notifications, instead of prompting.
+    pub fn set_notifications_allowed(&mut self, value: bool) -> WebDriverResult<()> {
+        self.set("profile.default_content_setting_values.notifications", content_setting(value))
+    }
+
+    /// Sets whether sites may access the geolocation, instead of prompting.
+    pub fn set_geolocation_allowed(&mut self, value: bool) -> WebDriverResult<()> {
+        self.set("profile.default_content_setting_values.geolocation", content_setting(value))
+    }
+
+    /// Sets the languages sent in the `Accept-Language` header, e.g. `de-DE,de`.
+    pub fn set_accept_languages(&mut self, languages: &str) -> WebDriverResult<()> {
+        self.set("intl.accept_languages", languages)
+    }
+}
+
+/// The value of a content setting: 1 allows and 2 blocks.
+fn content_setting(allow: bool) -> u8 {
+    if allow {
+        1
+    } else {
+        2
+    }
+}
+
+/// Performance logging preferences, for use with
+/// `ChromeCapabilities::set_perf_logging_prefs()`.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfLoggingPrefs {
+    /// Whether to collect events from the Network domain. Defaults to true.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_network: Option<bool>,
+    /// Whether to collect events from the Page domain. Defaults to true.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_page: Option<bool>,
+    /// A comma-separated string of Chrome tracing categories to collect,
+    /// e.g. `devtools.timeline,v8`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_categories: Option<String>,
+    /// The requested number of milliseconds between DevTools trace buffer usage events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buffer_usage_reporting_interval: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_chrome_options() {
+        let mut caps = ChromeCapabilities::new();
+        caps.set_mobile_emulation(MobileEmulation::metrics(
+            MobileDeviceMetrics::new(360, 640, 3.0),
+            Some("test-agent"),
+        ))
+        .unwrap();
+        let mut prefs = ChromePreferences::new();
+        prefs.set_prompt_for_download(false).unwrap();
+        prefs.set_notifications_allowed(false).unwrap();
+        caps.set_preferences(prefs).unwrap();
+        caps.add_exclude_switch("--enable-automation").unwrap();
+        caps.add_exclude_switch("enable-automation").unwrap();
+
+        assert_eq!(
+            caps.get("goog:chromeOptions").unwrap(),
+            &json!({
+                "mobileEmulation": {
+                    "deviceMetrics": {
+                        "width": 360, "height": 640, "pixelRatio": 3.0, "touch": true, "mobile": true
+                    },
+                    "userAgent": "test-agent"
+                },
+                "prefs": {
+                    "download.prompt_for_download": false,
+                    "profile.default_content_setting_values.notifications": 2
+                },
+                "excludeSwitches": ["enable-automation"]
+            })
+        );
+
+        caps.unset_mobile_emulation().unwrap();
+        assert!(caps.get("goog:chromeOptions").unwrap().get("mobileEmulation").is_none());
+    }
+}
@@ -273,6 +273,12 @@ pub trait CapabilitiesHelper {
         self.add("elementScrollBehavior", behaviour)
     }
 
+    /// Set whether the session should open a WebDriver BiDi websocket.
+    /// This is required for `WebDriver::bidi()`.
+    fn set_web_socket_url(&mut self, enabled: bool) -> WebDriverResult<()> {
+        self.add("webSocketUrl", enabled)
+    }
+
     /// Get whether the session can interact with modal popups such as `window.alert`.
     fn handles_alerts(&self) -> Option<bool> {
         self.get("handlesAlerts").and_then(|x| x.as_bool())
@@ -1,6 +1,12 @@
+use crate::common::print::PrintOptions;
+use fantoccini::elements::ElementRef;
+use fantoccini::wd::WebDriverCompatibleCommand;
 use fantoccini::Locator;
+use http::Method;
+use serde_json::json;
 use std::fmt;
 use std::fmt::Debug;
+use url::{ParseError, Url};
 
 #[derive(Debug, Clone)]
 pub enum BySelector {
@@ -88,3 +94,52 @@ impl By {
         }
     }
 }
+
+/// W3C WebDriver commands that are not (yet) provided by `fantoccini`.
+///
+/// These can be sent via `SessionHandle::client.issue_cmd()`, although it is usually
+/// easier to use the corresponding methods on `WebDriver` and `WebElement`.
+#[derive(Debug)]
+pub enum W3CCommand {
+    Print(PrintOptions),
+    GetElementComputedRole(ElementRef),
+    GetElementComputedLabel(ElementRef),
+    PerformActions(serde_json::Value),
+    /// Not part of the W3C spec, but supported by chromedriver and some other drivers.
+    GetSessionCapabilities,
+    /// The legacy log endpoint, supported by chromedriver and Selenium.
+    GetLog(String),
+}
+
+impl WebDriverCompatibleCommand for W3CCommand {
+    fn endpoint(&self, base_url: &Url, session_id: Option<&str>) -> Result<Url, ParseError> {
+        let base = { base_url.join(&format!("session/{}/", session_id.as_ref().unwrap()))? };
+        match &self {
+            W3CCommand::GetSessionCapabilities => {
+                base_url.join(&format!("session/{}", session_id.as_ref().unwrap()))
+            }
+            W3CCommand::Print(_) => base.join("print"),
+            W3CCommand::GetElementComputedRole(element_id) => {
+                base.join(&format!("element/{}/computedrole", element_id))
+            }
+            W3CCommand::GetElementComputedLabel(element_id) => {
+                base.join(&format!("element/{}/computedlabel", element_id))
+            }
+            W3CCommand::PerformActions(_) => base.join("actions"),
+            W3CCommand::GetLog(_) => base.join("se/log"),
+        }
+    }
+
+    fn method_and_body(&self, _request_url: &Url) -> (Method, Option<String>) {
+        match &self {
+            W3CCommand::Print(options) => (Method::POST, Some(json!(options).to_string())),
+            W3CCommand::PerformActions(actions) => (Method::POST, Some(actions.to_string())),
+            W3CCommand::GetLog(log_type) => {
+                (Method::POST, Some(json!({ "type": log_type }).to_string()))
+            }
+            W3CCommand::GetElementComputedRole(_)
+            | W3CCommand::GetElementComputedLabel(_)
+            | W3CCommand::GetSessionCapabilities => (Method::GET, None),
+        }
+    }
+}
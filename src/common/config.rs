@@ -1,18 +1,304 @@
-use crate::error::WebDriverResult;
+use crate::error::{WebDriverError, WebDriverResult};
 use crate::query::ElementPoller;
 use crate::SessionId;
 use fantoccini::wd::Capabilities;
 use parking_lot::RwLock;
 use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Prefix used for all environment variables read by [`WebDriverSettings::apply_env()`].
+pub const ENV_PREFIX: &str = "THIRTYFOUR_";
+
+/// Default polling and error handling for `ElementQuery` and `ElementWaiter`.
+///
+/// In a TOML/JSON file or an environment variable, the timeout and interval are
+/// written in whole milliseconds, as `timeout_ms` and `interval_ms`.
+/// See [`QuerySettings::poller()`] for how these values map to an [`ElementPoller`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuerySettings {
+    /// Maximum time to poll for. `None` means no timeout.
+    #[serde(rename = "timeout_ms", with = "opt_millis")]
+    pub timeout: Option<Duration>,
+    /// Minimum time between the start of each poll attempt.
+    #[serde(rename = "interval_ms", with = "millis")]
+    pub interval: Duration,
+    /// Minimum number of poll attempts, regardless of the timeout.
+    pub min_tries: u32,
+    /// Default value for `ignore_errors()` on new queries and waiters.
+    pub ignore_errors: bool,
+}
+
+impl Default for QuerySettings {
+    fn default() -> Self {
+        let mut settings = Self {
+            timeout: None,
+            interval: Duration::ZERO,
+            min_tries: 0,
+            ignore_errors: true,
+        };
+        settings.set_poller(ElementPoller::default());
+        settings
+    }
+}
+
+impl QuerySettings {
+    /// Get the `ElementPoller` described by these settings.
+    pub fn poller(&self) -> ElementPoller {
+        let interval = self.interval;
+        match (self.timeout, self.min_tries) {
+            (None, 0) => ElementPoller::NoWait,
+            (None, n) => ElementPoller::NumTriesWithInterval(n, interval),
+            (Some(t), 0) => ElementPoller::TimeoutWithInterval(t, interval),
+            (Some(t), n) => ElementPoller::TimeoutWithIntervalAndMinTries(t, interval, n),
+        }
+    }
+
+    /// Update these settings to describe the specified `ElementPoller`.
+    pub fn set_poller(&mut self, poller: ElementPoller) {
+        match poller {
+            ElementPoller::NoWait => {
+                self.timeout = None;
+                self.min_tries = 0;
+            }
+            ElementPoller::TimeoutWithInterval(timeout, interval) => {
+                self.timeout = Some(timeout);
+                self.interval = interval;
+                self.min_tries = 0;
+            }
+            ElementPoller::NumTriesWithInterval(num_tries, interval) => {
+                self.timeout = None;
+                self.interval = interval;
+                self.min_tries = num_tries;
+            }
+            ElementPoller::TimeoutWithIntervalAndMinTries(timeout, interval, num_tries) => {
+                self.timeout = Some(timeout);
+                self.interval = interval;
+                self.min_tries = num_tries;
+            }
+        }
+    }
+}
+
+/// (De)serialize a `Duration` as whole milliseconds.
+mod millis {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(d: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(d.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
+/// (De)serialize an `Option<Duration>` as whole milliseconds.
+mod opt_millis {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(
+        d: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match d {
+            Some(d) => serializer.serialize_some(&(d.as_millis() as u64)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Duration>, D::Error> {
+        Option::<u64>::deserialize(deserializer).map(|ms| ms.map(Duration::from_millis))
+    }
+}
+
+/// Settings that affect element interactions such as clicks and key presses.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InteractionSettings {
+    /// Scroll elements into view before clicking them.
+    pub scroll_before_click: bool,
+    /// Delay after each interaction, in milliseconds. Useful when watching a test run.
+    pub slow_motion_ms: u64,
+}
+
+impl InteractionSettings {
+    /// Get the slow-motion delay, if one is configured.
+    pub fn slow_motion(&self) -> Option<Duration> {
+        match self.slow_motion_ms {
+            0 => None,
+            ms => Some(Duration::from_millis(ms)),
+        }
+    }
+}
+
+/// Settings for automatic screenshots.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreenshotSettings {
+    /// If set, a screenshot is saved into this directory whenever an element query
+    /// or explicit wait fails.
+    pub on_failure_dir: Option<PathBuf>,
+}
+
+/// Typed settings for a WebDriver session.
+///
+/// Settings can be loaded from a TOML or JSON file, and/or from environment variables,
+/// which allows tuning the behaviour of a test suite (e.g. on CI) without recompiling.
+///
+/// An example TOML file (all keys are optional):
+/// ```toml
+/// [query]
+/// timeout_ms = 20000
+/// interval_ms = 500
+/// min_tries = 0
+/// ignore_errors = true
+///
+/// [interaction]
+/// scroll_before_click = false
+/// slow_motion_ms = 0
+///
+/// [screenshot]
+/// on_failure_dir = "target/screenshots"
+/// ```
+///
+/// The equivalent environment variables are `THIRTYFOUR_QUERY_TIMEOUT_MS`,
+/// `THIRTYFOUR_QUERY_INTERVAL_MS`, `THIRTYFOUR_QUERY_MIN_TRIES`,
+/// `THIRTYFOUR_QUERY_IGNORE_ERRORS`, `THIRTYFOUR_INTERACTION_SCROLL_BEFORE_CLICK`,
+/// `THIRTYFOUR_INTERACTION_SLOW_MOTION_MS` and `THIRTYFOUR_SCREENSHOT_ON_FAILURE_DIR`.
+/// Set an optional value to `none` (or an empty string) to unset it.
+///
+/// # Example:
+/// ```ignore
+/// let settings = WebDriverSettings::from_file(Path::new("thirtyfour.toml"))?;
+/// driver.config().set_settings(settings);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebDriverSettings {
+    pub query: QuerySettings,
+    pub interaction: InteractionSettings,
+    pub screenshot: ScreenshotSettings,
+}
+
+impl WebDriverSettings {
+    /// Create new settings with the default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse settings from a TOML string.
+    pub fn from_toml(s: &str) -> WebDriverResult<Self> {
+        toml::from_str(s).map_err(|e| WebDriverError::ConfigError(e.to_string()))
+    }
+
+    /// Parse settings from a JSON string.
+    pub fn from_json(s: &str) -> WebDriverResult<Self> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    /// Load settings from the specified file, and then apply any overrides from
+    /// environment variables.
+    ///
+    /// Files with a `.json` extension are parsed as JSON. All other files are parsed as TOML.
+    pub fn from_file(path: &Path) -> WebDriverResult<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let mut settings = match path.extension().and_then(|x| x.to_str()) {
+            Some("json") => Self::from_json(&contents)?,
+            _ => Self::from_toml(&contents)?,
+        };
+        settings.apply_env()?;
+        Ok(settings)
+    }
+
+    /// Create the default settings, with any overrides from environment variables applied.
+    pub fn from_env() -> WebDriverResult<Self> {
+        let mut settings = Self::default();
+        settings.apply_env()?;
+        Ok(settings)
+    }
+
+    /// Override these settings with any `THIRTYFOUR_*` environment variables that are set.
+    pub fn apply_env(&mut self) -> WebDriverResult<()> {
+        self.apply_vars(std::env::vars())
+    }
+
+    /// Override these settings using the specified `(name, value)` pairs.
+    /// Names must include the `THIRTYFOUR_` prefix. Unknown names are ignored.
+    pub fn apply_vars<I, K, V>(&mut self, vars: I) -> WebDriverResult<()>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        for (name, value) in vars {
+            let key = match name.as_ref().strip_prefix(ENV_PREFIX) {
+                Some(x) => x,
+                None => continue,
+            };
+            let value = value.as_ref();
+            match key {
+                "QUERY_TIMEOUT_MS" => {
+                    self.query.timeout = parse_opt_var(key, value)?.map(Duration::from_millis)
+                }
+                "QUERY_INTERVAL_MS" => {
+                    self.query.interval = Duration::from_millis(parse_var(key, value)?)
+                }
+                "QUERY_MIN_TRIES" => self.query.min_tries = parse_var(key, value)?,
+                "QUERY_IGNORE_ERRORS" => self.query.ignore_errors = parse_var(key, value)?,
+                "INTERACTION_SCROLL_BEFORE_CLICK" => {
+                    self.interaction.scroll_before_click = parse_var(key, value)?
+                }
+                "INTERACTION_SLOW_MOTION_MS" => {
+                    self.interaction.slow_motion_ms = parse_var(key, value)?
+                }
+                "SCREENSHOT_ON_FAILURE_DIR" => {
+                    self.screenshot.on_failure_dir = parse_opt_var(key, value)?
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_var<T>(key: &str, value: &str) -> WebDriverResult<T>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    value.trim().parse().map_err(|e| {
+        WebDriverError::ConfigError(format!("invalid value for {}{}: {}", ENV_PREFIX, key, e))
+    })
+}
+
+fn parse_opt_var<T>(key: &str, value: &str) -> WebDriverResult<Option<T>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    match value.trim() {
+        "" | "none" => Ok(None),
+        _ => parse_var(key, value).map(Some),
+    }
+}
 
 #[derive(Debug)]
 struct InnerConfig {
     pub session_id: SessionId,
-    pub query_poller: ElementPoller,
+    pub settings: WebDriverSettings,
     pub capabilities: Capabilities,
+    pub server_url: Option<String>,
+    pub websocket_url: Option<String>,
     pub custom_settings: HashMap<String, serde_json::Value>,
 }
 
@@ -26,8 +312,10 @@ impl WebDriverConfig {
         Self {
             config: Arc::new(RwLock::new(InnerConfig {
                 session_id,
-                query_poller: ElementPoller::default(),
+                settings: WebDriverSettings::default(),
                 capabilities,
+                server_url: None,
+                websocket_url: None,
                 custom_settings: HashMap::default(),
             })),
         }
@@ -40,12 +328,24 @@ impl WebDriverConfig {
 
     pub fn get_query_poller(&self) -> ElementPoller {
         let cfg = self.config.read();
-        cfg.query_poller.clone()
+        cfg.settings.query.poller()
     }
 
     pub fn set_query_poller(&self, poller: ElementPoller) {
         let mut cfg = self.config.write();
-        cfg.query_poller = poller;
+        cfg.settings.query.set_poller(poller);
+    }
+
+    /// Get a copy of the current typed settings.
+    pub fn get_settings(&self) -> WebDriverSettings {
+        let cfg = self.config.read();
+        cfg.settings.clone()
+    }
+
+    /// Replace the typed settings. This affects all handles sharing this config.
+    pub fn set_settings(&self, settings: WebDriverSettings) {
+        let mut cfg = self.config.write();
+        cfg.settings = settings;
     }
 
     pub fn get_capabilities(&self) -> Capabilities {
@@ -53,6 +353,33 @@ impl WebDriverConfig {
         cfg.capabilities.clone()
     }
 
+    /// Get the URL of the WebDriver server this session was created on.
+    pub fn get_server_url(&self) -> Option<String> {
+        let cfg = self.config.read();
+        cfg.server_url.clone()
+    }
+
+    pub(crate) fn set_server_url(&self, server_url: &str) {
+        let mut cfg = self.config.write();
+        cfg.server_url = Some(server_url.to_string());
+    }
+
+    /// Get the BiDi websocket URL for this session, if known.
+    ///
+    /// This is the `webSocketUrl` capability returned when the session was created,
+    /// unless it was overridden with `set_websocket_url()`.
+    pub fn get_websocket_url(&self) -> Option<String> {
+        let cfg = self.config.read();
+        cfg.websocket_url.clone()
+    }
+
+    /// Set the BiDi websocket URL to connect to, e.g. when the driver is behind a proxy
+    /// and the URL it returned is not reachable.
+    pub fn set_websocket_url(&self, websocket_url: &str) {
+        let mut cfg = self.config.write();
+        cfg.websocket_url = Some(websocket_url.to_string());
+    }
+
     pub fn get<V>(&self, key: &str) -> Option<V>
     where
         V: DeserializeOwned,
@@ -61,6 +388,21 @@ impl WebDriverConfig {
         cfg.custom_settings.get(key).and_then(|v| serde_json::from_value::<V>(v.clone()).ok())
     }
 
+    /// Get the custom setting with the specified key.
+    ///
+    /// Returns `Ok(None)` if the key does not exist, or an error if the stored value
+    /// could not be deserialized into the requested type.
+    pub fn try_get<V>(&self, key: &str) -> WebDriverResult<Option<V>>
+    where
+        V: DeserializeOwned,
+    {
+        let cfg = self.config.read();
+        match cfg.custom_settings.get(key) {
+            Some(v) => Ok(Some(serde_json::from_value::<V>(v.clone())?)),
+            None => Ok(None),
+        }
+    }
+
     pub fn set<V>(&mut self, key: &str, value: V) -> WebDriverResult<()>
     where
         V: Serialize,
@@ -70,3 +412,75 @@ impl WebDriverConfig {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_from_toml() {
+        let settings = WebDriverSettings::from_toml(
+            r#"
+            [query]
+            timeout_ms = 5000
+            ignore_errors = false
+
+            [screenshot]
+            on_failure_dir = "shots"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(settings.query.timeout, Some(Duration::from_secs(5)));
+        assert_eq!(settings.query.interval, Duration::from_millis(500));
+        assert!(!settings.query.ignore_errors);
+        assert!(!settings.interaction.scroll_before_click);
+        assert_eq!(settings.screenshot.on_failure_dir, Some(PathBuf::from("shots")));
+    }
+
+    #[test]
+    fn test_settings_apply_vars() {
+        let mut settings =
+            WebDriverSettings::from_json(r#"{"interaction": {"slow_motion_ms": 10}}"#).unwrap();
+        settings
+            .apply_vars(vec![
+                ("THIRTYFOUR_QUERY_TIMEOUT_MS", "none"),
+                ("THIRTYFOUR_QUERY_MIN_TRIES", "3"),
+                ("THIRTYFOUR_INTERACTION_SCROLL_BEFORE_CLICK", "true"),
+                ("OTHER_VAR", "ignored"),
+            ])
+            .unwrap();
+        assert_eq!(settings.interaction.slow_motion(), Some(Duration::from_millis(10)));
+        assert!(settings.interaction.scroll_before_click);
+        assert!(matches!(
+            settings.query.poller(),
+            ElementPoller::NumTriesWithInterval(3, i) if i == Duration::from_millis(500)
+        ));
+
+        let err = settings.apply_vars(vec![("THIRTYFOUR_QUERY_MIN_TRIES", "abc")]);
+        assert!(matches!(err, Err(WebDriverError::ConfigError(_))));
+    }
+
+    #[test]
+    fn test_query_poller_keeps_sub_millisecond_durations() {
+        let config = WebDriverConfig::new(SessionId::from("id"), Capabilities::new());
+        config.set_query_poller(ElementPoller::TimeoutWithInterval(
+            Duration::from_micros(1500),
+            Duration::from_micros(250),
+        ));
+        assert!(matches!(
+            config.get_query_poller(),
+            ElementPoller::TimeoutWithInterval(t, i)
+                if t == Duration::from_micros(1500) && i == Duration::from_micros(250)
+        ));
+    }
+
+    #[test]
+    fn test_custom_settings() {
+        let mut config = WebDriverConfig::new(SessionId::from("id"), Capabilities::new());
+        config.set("retries", "three").unwrap();
+        assert_eq!(config.get::<String>("retries"), Some("three".to_string()));
+        assert_eq!(config.get::<u32>("retries"), None);
+        assert!(config.try_get::<u32>("retries").is_err());
+        assert_eq!(config.try_get::<u32>("missing").unwrap(), None);
+    }
+}
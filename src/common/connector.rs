@@ -0,0 +1,180 @@
+//! The HTTP connector used for new sessions.
+//!
+//! fantoccini does not expose the response to the new session command, which contains
+//! the capabilities chosen by the driver (such as the BiDi `webSocketUrl`). Until the
+//! session is created, this connector gives fantoccini an in-memory connection served
+//! by a small proxy, which sends each request to the driver with a hyper client and
+//! reads the capabilities from the new session response on the way back.
+
+use hyper::client::connect::{Connected, Connection};
+use hyper::client::HttpConnector;
+use hyper::header::TRANSFER_ENCODING;
+use hyper::server::conn::Http;
+use hyper::service::{service_fn, Service};
+use hyper::{Body, Client, Method, Request, Response, Uri};
+use hyper_tls::{HttpsConnector, MaybeHttpsStream};
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::error::Error;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, DuplexStream, ReadBuf};
+use tokio::net::TcpStream;
+
+type BoxError = Box<dyn Error + Send + Sync>;
+
+/// An HTTPS (or HTTP) connector that proxies connections until
+/// [`SessionConnector::take_session_capabilities()`] is called.
+#[derive(Clone)]
+pub(crate) struct SessionConnector {
+    inner: HttpsConnector<HttpConnector>,
+    client: Client<HttpsConnector<HttpConnector>>,
+    capabilities: Arc<Mutex<Option<Value>>>,
+    proxying: Arc<AtomicBool>,
+}
+
+impl SessionConnector {
+    pub fn new() -> Self {
+        let inner = HttpsConnector::new();
+        Self {
+            client: Client::builder().build(inner.clone()),
+            inner,
+            capabilities: Arc::new(Mutex::new(None)),
+            proxying: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    /// Stop proxying new connections and return the capabilities from the new session
+    /// response, or `Value::Null` if the response did not contain any.
+    pub fn take_session_capabilities(&self) -> Value {
+        self.proxying.store(false, Ordering::SeqCst);
+        self.capabilities.lock().take().unwrap_or_default()
+    }
+
+    /// Send the request to the driver at `base`, and record the capabilities if it
+    /// creates a new session.
+    async fn forward(
+        client: Client<HttpsConnector<HttpConnector>>,
+        base: Uri,
+        capabilities: Arc<Mutex<Option<Value>>>,
+        mut request: Request<Body>,
+    ) -> Result<Response<Body>, BoxError> {
+        let is_new_session = request.method() == Method::POST
+            && request.uri().path().trim_end_matches('/').ends_with("/session");
+        let mut uri = Uri::builder();
+        if let Some(scheme) = base.scheme() {
+            uri = uri.scheme(scheme.clone());
+        }
+        if let Some(authority) = base.authority() {
+            uri = uri.authority(authority.clone());
+        }
+        if let Some(path) = request.uri().path_and_query() {
+            uri = uri.path_and_query(path.clone());
+        }
+        *request.uri_mut() = uri.build()?;
+
+        let response = client.request(request).await?;
+        if !is_new_session {
+            return Ok(response);
+        }
+        let (mut parts, body) = response.into_parts();
+        let body = hyper::body::to_bytes(body).await?;
+        if let Ok(mut v) = serde_json::from_slice::<Value>(&body) {
+            *capabilities.lock() = Some(v["value"]["capabilities"].take());
+        }
+        // The body is sent on in one piece, so let hyper set the content length.
+        parts.headers.remove(TRANSFER_ENCODING);
+        Ok(Response::from_parts(parts, Body::from(body)))
+    }
+}
+
+impl Service<Uri> for SessionConnector {
+    type Response = SessionStream;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        if !self.proxying.load(Ordering::SeqCst) {
+            let connecting = self.inner.call(uri);
+            return Box::pin(async move { Ok(SessionStream::Direct(connecting.await?)) });
+        }
+
+        let (stream, proxy_stream) = tokio::io::duplex(64 * 1024);
+        let client = self.client.clone();
+        let capabilities = self.capabilities.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                Self::forward(client.clone(), uri.clone(), capabilities.clone(), request)
+            });
+            if let Err(e) =
+                Http::new().http1_only(true).serve_connection(proxy_stream, service).await
+            {
+                log::debug!("new session proxy connection failed: {}", e);
+            }
+        });
+        Box::pin(async move { Ok(SessionStream::Proxied(stream)) })
+    }
+}
+
+/// A connection to the driver, either direct or via the new session proxy.
+pub(crate) enum SessionStream {
+    Direct(MaybeHttpsStream<TcpStream>),
+    Proxied(DuplexStream),
+}
+
+impl AsyncRead for SessionStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            SessionStream::Direct(s) => Pin::new(s).poll_read(cx, buf),
+            SessionStream::Proxied(s) => Pin::new(s).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for SessionStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            SessionStream::Direct(s) => Pin::new(s).poll_write(cx, buf),
+            SessionStream::Proxied(s) => Pin::new(s).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            SessionStream::Direct(s) => Pin::new(s).poll_flush(cx),
+            SessionStream::Proxied(s) => Pin::new(s).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            SessionStream::Direct(s) => Pin::new(s).poll_shutdown(cx),
+            SessionStream::Proxied(s) => Pin::new(s).poll_shutdown(cx),
+        }
+    }
+}
+
+impl Connection for SessionStream {
+    fn connected(&self) -> Connected {
+        match self {
+            SessionStream::Direct(s) => s.connected(),
+            SessionStream::Proxied(_) => Connected::new(),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! A minimal in-process WebDriver server for unit tests.
+//!
+//! It accepts a new session, records every request, and answers each one with the
+//! response chosen by the test (or `null` by default).
+
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use fantoccini::wd::Capabilities;
+
+use crate::{DesiredCapabilities, WebDriver};
+
+/// The session id of every session created by the fake driver.
+pub(crate) const SESSION_ID: &str = "fake-session";
+
+/// The `webSocketUrl` returned for sessions that request one.
+pub(crate) const WEBSOCKET_URL: &str = "ws://127.0.0.1:9222/session/fake-session";
+
+/// A request received by the fake driver.
+#[derive(Debug, Clone)]
+pub(crate) struct RecordedRequest {
+    pub method: String,
+    /// The path relative to the session, e.g. `actions` for `/session/{id}/actions`.
+    pub path: String,
+    pub body: Value,
+}
+
+/// The status code and `value` to send for a request, or `None` for the default response.
+type Responder = dyn Fn(&RecordedRequest) -> Option<(u16, Value)> + Send + Sync;
+
+pub(crate) struct FakeWebDriver {
+    url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl FakeWebDriver {
+    /// Start a fake driver that answers every request with the default response.
+    pub async fn start() -> Self {
+        Self::start_with(|_| None).await
+    }
+
+    /// Start a fake driver that uses `respond` to choose the response to each request.
+    pub async fn start_with<F>(respond: F) -> Self
+    where
+        F: Fn(&RecordedRequest) -> Option<(u16, Value)> + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let respond: Arc<Responder> = Arc::new(respond);
+        let task_requests = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve(stream, task_requests.clone(), respond.clone()));
+            }
+        });
+        Self {
+            url,
+            requests,
+        }
+    }
+
+    /// Create a Chrome session on the fake driver.
+    pub async fn connect(&self) -> WebDriver {
+        self.connect_with(DesiredCapabilities::chrome()).await
+    }
+
+    /// Create a session with the specified capabilities on the fake driver.
+    pub async fn connect_with(&self, capabilities: impl Into<Capabilities>) -> WebDriver {
+        WebDriver::new(&self.url, capabilities).await.unwrap()
+    }
+
+    /// All requests received so far, except for session creation.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().clone()
+    }
+
+    /// The body of the last request with the specified method and path.
+    pub fn last_body(&self, method: &str, path: &str) -> Option<Value> {
+        self.requests()
+            .into_iter()
+            .rev()
+            .find(|r| r.method == method && r.path == path)
+            .map(|r| r.body)
+    }
+}
+
+async fn serve(
+    stream: TcpStream,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    respond: Arc<Responder>,
+) {
+    let mut reader = BufReader::new(stream);
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).await.unwrap_or(0) == 0 {
+            return;
+        }
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_string();
+        let full_path = parts.next().unwrap_or_default().to_string();
+
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).await.unwrap_or(0) == 0 {
+                return;
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        if reader.read_exact(&mut body).await.is_err() {
+            return;
+        }
+        let body = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+        let (status, value) = if method == "POST" && full_path.trim_end_matches('/') == "/session" {
+            let mut capabilities = json!({"browserName": "chrome"});
+            if body["capabilities"]["alwaysMatch"]["webSocketUrl"] == json!(true) {
+                capabilities["webSocketUrl"] = json!(WEBSOCKET_URL);
+            }
+            (200, json!({"sessionId": SESSION_ID, "capabilities": capabilities}))
+        } else {
+            let prefix = format!("/session/{}", SESSION_ID);
+            let path = full_path.strip_prefix(&prefix).unwrap_or(&full_path);
+            let request = RecordedRequest {
+                method,
+                path: path.trim_start_matches('/').to_string(),
+                body,
+            };
+            let response = respond(&request).unwrap_or((200, Value::Null));
+            requests.lock().push(request);
+            response
+        };
+
+        let payload = json!({ "value": value }).to_string();
+        let response = format!(
+            "HTTP/1.1 {} Fake\r\nContent-Type: application/json; charset=utf-8\r\n\
+             Content-Length: {}\r\n\r\n{}",
+            status,
+            payload.len(),
+            payload
+        );
+        if reader.get_mut().write_all(response.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize, Serializer};
+
+/// Page orientation for printing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrintOrientation {
+    #[default]
+    Portrait,
+    Landscape,
+}
+
+/// Paper size for printing, in centimetres.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrintPage {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Default for PrintPage {
+    /// US Letter, as per the W3C spec.
+    fn default() -> Self {
+        Self {
+            width: 21.59,
+            height: 27.94,
+        }
+    }
+}
+
+impl PrintPage {
+    /// Create a new page size, in centimetres.
+    pub fn new(width: f64, height: f64) -> Self {
+        Self {
+            width,
+            height,
+        }
+    }
+
+    /// ISO A4 (21.0 x 29.7 cm).
+    pub fn a4() -> Self {
+        Self::new(21.0, 29.7)
+    }
+
+    /// US Letter (21.59 x 27.94 cm).
+    pub fn letter() -> Self {
+        Self::default()
+    }
+}
+
+/// Page margins for printing, in centimetres.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrintMargins {
+    pub top: f64,
+    pub bottom: f64,
+    pub left: f64,
+    pub right: f64,
+}
+
+impl Default for PrintMargins {
+    fn default() -> Self {
+        Self::uniform(1.0)
+    }
+}
+
+impl PrintMargins {
+    /// Use the same margin (in centimetres) on all sides.
+    pub fn uniform(margin: f64) -> Self {
+        Self {
+            top: margin,
+            bottom: margin,
+            left: margin,
+            right: margin,
+        }
+    }
+}
+
+/// A single page, or an inclusive range of pages, to print. Page numbers start at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageRange {
+    Page(u32),
+    Range(u32, u32),
+}
+
+impl Serialize for PageRange {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            PageRange::Page(page) => serializer.serialize_u32(*page),
+            PageRange::Range(first, last) => {
+                serializer.serialize_str(&format!("{}-{}", first, last))
+            }
+        }
+    }
+}
+
+/// Options for the W3C Print Page command.
+///
+/// The defaults match those defined by the W3C WebDriver spec.
+///
+/// # Example:
+/// ```rust
+/// use thirtyfour::{PageRange, PrintOptions, PrintOrientation, PrintPage};
+///
+/// let mut options = PrintOptions::new();
+/// options.orientation = PrintOrientation::Landscape;
+/// options.page = PrintPage::a4();
+/// options.background = true;
+/// options.page_ranges = vec![PageRange::Page(1), PageRange::Range(3, 5)];
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrintOptions {
+    pub orientation: PrintOrientation,
+    /// Scale factor, between 0.1 and 2.0.
+    pub scale: f64,
+    /// Print background graphics.
+    pub background: bool,
+    pub page: PrintPage,
+    pub margin: PrintMargins,
+    /// Shrink the content to fit the page width.
+    pub shrink_to_fit: bool,
+    /// The pages to print. An empty list prints all pages.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub page_ranges: Vec<PageRange>,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self {
+            orientation: PrintOrientation::default(),
+            scale: 1.0,
+            background: false,
+            page: PrintPage::default(),
+            margin: PrintMargins::default(),
+            shrink_to_fit: true,
+            page_ranges: Vec::new(),
+        }
+    }
+}
+
+impl PrintOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
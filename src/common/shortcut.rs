@@ -0,0 +1,193 @@
+use fantoccini::key::Key;
+use std::str::FromStr;
+
+use crate::error::{WebDriverError, WebDriverResult};
+
+/// A single key within a keyboard shortcut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutKey {
+    /// The platform's primary modifier: `Meta` (Cmd) on macOS and iOS, `Control` elsewhere.
+    Primary,
+    /// A specific key.
+    Key(char),
+}
+
+impl ShortcutKey {
+    /// Resolve this key to a character, using the specified primary modifier.
+    pub fn resolve(&self, primary: Key) -> char {
+        match self {
+            ShortcutKey::Primary => primary.into(),
+            ShortcutKey::Key(c) => *c,
+        }
+    }
+}
+
+/// A keyboard shortcut (chord), such as `Ctrl+Shift+K`.
+///
+/// All keys are pressed in order and then released in reverse order.
+/// Key names are case-insensitive, and `Primary`, `Mod` or `CmdOrCtrl` can be used for
+/// the platform's primary modifier, which is resolved when the shortcut is sent.
+///
+/// Letters are sent in lowercase, unless the shortcut also includes `Shift`.
+///
+/// # Example:
+/// ```rust
+/// use thirtyfour::Shortcut;
+/// # use thirtyfour::prelude::*;
+///
+/// # fn main() -> WebDriverResult<()> {
+/// let shortcut: Shortcut = "Ctrl+Shift+K".parse()?;
+/// let select_all: Shortcut = "Primary+A".parse()?;
+/// let zoom_in: Shortcut = "Ctrl++".parse()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shortcut {
+    keys: Vec<ShortcutKey>,
+}
+
+impl Shortcut {
+    /// Parse a shortcut such as `Ctrl+Shift+K`.
+    pub fn parse(shortcut: &str) -> WebDriverResult<Self> {
+        let invalid = || WebDriverError::CustomError(format!("invalid shortcut: '{}'", shortcut));
+
+        // Allow '+' itself as the last key, e.g. "Ctrl++".
+        let (rest, plus) = match shortcut.strip_suffix("++") {
+            Some(rest) => (rest, true),
+            None if shortcut == "+" => ("", true),
+            None => (shortcut, false),
+        };
+
+        let mut names: Vec<&str> = Vec::new();
+        if !rest.is_empty() {
+            names.extend(rest.split('+').map(str::trim));
+        }
+        if plus {
+            names.push("+");
+        }
+        if names.is_empty() || names.iter().any(|n| n.is_empty()) {
+            return Err(invalid());
+        }
+
+        let shift = names.iter().any(|n| n.eq_ignore_ascii_case("shift"));
+        let keys = names
+            .into_iter()
+            .map(|name| parse_key(name, shift).ok_or_else(invalid))
+            .collect::<WebDriverResult<Vec<_>>>()?;
+        Ok(Self {
+            keys,
+        })
+    }
+
+    /// The keys in this shortcut, in the order they are pressed.
+    pub fn keys(&self) -> &[ShortcutKey] {
+        &self.keys
+    }
+
+    /// Resolve all keys in this shortcut to characters, using the specified primary modifier.
+    pub fn resolve(&self, primary: Key) -> Vec<char> {
+        self.keys.iter().map(|k| k.resolve(primary)).collect()
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = WebDriverError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+const NAMED_KEYS: &[(&str, Key)] = &[
+    ("ctrl", Key::Control),
+    ("control", Key::Control),
+    ("shift", Key::Shift),
+    ("alt", Key::Alt),
+    ("option", Key::Alt),
+    ("meta", Key::Meta),
+    ("cmd", Key::Meta),
+    ("command", Key::Meta),
+    ("win", Key::Meta),
+    ("super", Key::Meta),
+    ("enter", Key::Enter),
+    ("return", Key::Return),
+    ("tab", Key::Tab),
+    ("esc", Key::Escape),
+    ("escape", Key::Escape),
+    ("space", Key::Space),
+    ("backspace", Key::Backspace),
+    ("delete", Key::Delete),
+    ("del", Key::Delete),
+    ("insert", Key::Insert),
+    ("home", Key::Home),
+    ("end", Key::End),
+    ("pageup", Key::PageUp),
+    ("pagedown", Key::PageDown),
+    ("up", Key::Up),
+    ("down", Key::Down),
+    ("left", Key::Left),
+    ("right", Key::Right),
+    ("arrowup", Key::Up),
+    ("arrowdown", Key::Down),
+    ("arrowleft", Key::Left),
+    ("arrowright", Key::Right),
+    ("f1", Key::F1),
+    ("f2", Key::F2),
+    ("f3", Key::F3),
+    ("f4", Key::F4),
+    ("f5", Key::F5),
+    ("f6", Key::F6),
+    ("f7", Key::F7),
+    ("f8", Key::F8),
+    ("f9", Key::F9),
+    ("f10", Key::F10),
+    ("f11", Key::F11),
+    ("f12", Key::F12),
+];
+
+fn parse_key(name: &str, shift: bool) -> Option<ShortcutKey> {
+    let lower = name.to_ascii_lowercase();
+    if matches!(lower.as_str(), "primary" | "mod" | "cmdorctrl") {
+        return Some(ShortcutKey::Primary);
+    }
+    if let Some((_, key)) = NAMED_KEYS.iter().find(|(n, _)| *n == lower) {
+        return Some(ShortcutKey::Key((*key).into()));
+    }
+
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if shift => Some(ShortcutKey::Key(c.to_ascii_uppercase())),
+        (Some(c), None) => Some(ShortcutKey::Key(c.to_ascii_lowercase())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_shortcuts() {
+        let shortcut = Shortcut::parse("Ctrl+Shift+K").unwrap();
+        assert_eq!(
+            shortcut.keys(),
+            &[
+                ShortcutKey::Key(Key::Control.into()),
+                ShortcutKey::Key(Key::Shift.into()),
+                ShortcutKey::Key('K')
+            ]
+        );
+
+        let shortcut = Shortcut::parse("primary+A").unwrap();
+        assert_eq!(shortcut.keys(), &[ShortcutKey::Primary, ShortcutKey::Key('a')]);
+        assert_eq!(shortcut.resolve(Key::Meta), vec![Key::Meta.into(), 'a']);
+
+        let shortcut = Shortcut::parse("Ctrl++").unwrap();
+        assert_eq!(shortcut.keys()[1], ShortcutKey::Key('+'));
+
+        assert!(Shortcut::parse("").is_err());
+        assert!(Shortcut::parse("Ctrl+").is_err());
+        assert!(Shortcut::parse("Ctrl+Foo").is_err());
+    }
+}
@@ -1,9 +1,10 @@
 use fantoccini::elements::ElementRef;
+use std::collections::BTreeMap;
 use std::{fmt, ops::Deref};
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ElementRect {
     pub x: f64,
     pub y: f64,
@@ -19,6 +20,91 @@ impl ElementRect {
     pub fn center(&self) -> (f64, f64) {
         (self.x + (self.width / 2.0), self.y + (self.height / 2.0))
     }
+
+    /// The x coordinate of the right edge.
+    pub fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    /// The y coordinate of the bottom edge.
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    pub fn area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    /// Return the overlapping area of the two rectangles, if any.
+    pub fn intersection(&self, other: &ElementRect) -> Option<ElementRect> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+        if right > x && bottom > y {
+            Some(ElementRect {
+                x,
+                y,
+                width: right - x,
+                height: bottom - y,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Return true if the two rectangles overlap.
+    pub fn intersects(&self, other: &ElementRect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Return true if the other rectangle lies entirely within this one.
+    pub fn contains(&self, other: &ElementRect) -> bool {
+        other.x >= self.x
+            && other.y >= self.y
+            && other.right() <= self.right()
+            && other.bottom() <= self.bottom()
+    }
+
+    /// Return true if the specified point lies within this rectangle.
+    pub fn contains_point(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x <= self.right() && y >= self.y && y <= self.bottom()
+    }
+
+    /// Return the shortest distance between the edges of the two rectangles,
+    /// or 0.0 if they overlap or touch.
+    pub fn distance(&self, other: &ElementRect) -> f64 {
+        let dx = (other.x - self.right()).max(self.x - other.right()).max(0.0);
+        let dy = (other.y - self.bottom()).max(self.y - other.bottom()).max(0.0);
+        dx.hypot(dy)
+    }
+
+    /// Return the distance between the centers of the two rectangles.
+    pub fn center_distance(&self, other: &ElementRect) -> f64 {
+        let (x1, y1) = self.center();
+        let (x2, y2) = other.center();
+        (x2 - x1).hypot(y2 - y1)
+    }
+}
+
+/// The state of an element at a single point in time, fetched in one round trip.
+///
+/// See [`WebElement::snapshot()`](crate::WebElement::snapshot).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElementSnapshot {
+    pub tag_name: String,
+    /// The rendered text (`innerText`) of the element.
+    pub text: String,
+    pub class_names: Vec<String>,
+    pub attributes: BTreeMap<String, String>,
+    /// The `value` property, for form elements.
+    pub value: Option<String>,
+    pub displayed: bool,
+    pub enabled: bool,
+    pub selected: bool,
+    /// The bounding rectangle, relative to the page like `WebElement::rect()`.
+    pub rect: ElementRect,
 }
 
 /// Helper to Deserialize ElementRef from JSON Value.
@@ -137,3 +223,31 @@ impl Rect {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> ElementRect {
+        ElementRect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn element_rect_geometry() {
+        let a = rect(0.0, 0.0, 100.0, 100.0);
+        let b = rect(50.0, 50.0, 100.0, 100.0);
+        assert_eq!(a.intersection(&b), Some(rect(50.0, 50.0, 50.0, 50.0)));
+        assert!(a.contains(&rect(10.0, 10.0, 20.0, 20.0)));
+        assert!(!a.contains(&b));
+
+        let c = rect(130.0, 140.0, 10.0, 10.0);
+        assert_eq!(a.intersection(&c), None);
+        assert_eq!(a.distance(&c), 50.0);
+        assert_eq!(a.distance(&b), 0.0);
+    }
+}
@@ -0,0 +1,147 @@
+use futures::channel::{mpsc, oneshot};
+use futures::stream::{SplitSink, SplitStream};
+use futures::{SinkExt, StreamExt};
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+use crate::error::{WebDriverError, WebDriverResult};
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// State shared with the task that reads from the websocket.
+#[derive(Default)]
+struct Shared {
+    pending: Mutex<HashMap<u64, oneshot::Sender<Value>>>,
+    subscribers: Mutex<Vec<mpsc::UnboundedSender<Value>>>,
+}
+
+struct Inner {
+    sink: tokio::sync::Mutex<SplitSink<WsStream, Message>>,
+    shared: Arc<Shared>,
+    next_id: AtomicU64,
+    /// Stops the reader task (and closes the socket) when the last connection is dropped.
+    _shutdown: oneshot::Sender<()>,
+}
+
+/// A JSON message transport over a websocket, shared by the CDP and BiDi clients.
+///
+/// Both protocols tag each command with an `id` that is echoed in its response, and
+/// send events as messages without an `id`.
+#[derive(Clone)]
+pub(crate) struct WebSocketConnection {
+    inner: Arc<Inner>,
+}
+
+impl WebSocketConnection {
+    pub async fn connect(url: &str) -> WebDriverResult<Self> {
+        let (ws, _) = connect_async(url).await.map_err(|e| {
+            WebDriverError::CustomError(format!("unable to connect to {}: {}", url, e))
+        })?;
+        let (sink, stream) = ws.split();
+        let shared = Arc::new(Shared::default());
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        tokio::spawn(read_messages(stream, shared.clone(), shutdown_rx));
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                sink: tokio::sync::Mutex::new(sink),
+                shared,
+                next_id: AtomicU64::new(1),
+                _shutdown: shutdown_tx,
+            }),
+        })
+    }
+
+    /// Send the specified command object with a new `id`, and return the response
+    /// message with the same `id`.
+    pub async fn send(&self, mut command: Value) -> WebDriverResult<Value> {
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        command["id"] = id.into();
+
+        let (tx, rx) = oneshot::channel();
+        self.inner.shared.pending.lock().insert(id, tx);
+        let sent = self.inner.sink.lock().await.send(Message::Text(command.to_string())).await;
+        if let Err(e) = sent {
+            self.inner.shared.pending.lock().remove(&id);
+            return Err(WebDriverError::CustomError(format!("websocket error: {}", e)));
+        }
+
+        rx.await.map_err(|_| WebDriverError::CustomError("websocket connection closed".to_string()))
+    }
+
+    /// Receive all event messages (those without an `id`) from now on.
+    /// The receiver ends when the connection is closed.
+    pub fn events(&self) -> mpsc::UnboundedReceiver<Value> {
+        let (tx, rx) = mpsc::unbounded();
+        self.inner.shared.subscribers.lock().push(tx);
+        rx
+    }
+}
+
+async fn read_messages(
+    mut stream: SplitStream<WsStream>,
+    shared: Arc<Shared>,
+    mut shutdown: oneshot::Receiver<()>,
+) {
+    loop {
+        let message = tokio::select! {
+            message = stream.next() => message,
+            _ = &mut shutdown => break,
+        };
+        let text = match message {
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(Message::Binary(data))) => String::from_utf8_lossy(&data).into_owned(),
+            Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+            Some(Ok(_)) => continue,
+        };
+        let message: Value = match serde_json::from_str(&text) {
+            Ok(m) => m,
+            Err(e) => {
+                log::warn!("ignoring invalid websocket message: {}", e);
+                continue;
+            }
+        };
+
+        match message["id"].as_u64() {
+            Some(id) => {
+                if let Some(tx) = shared.pending.lock().remove(&id) {
+                    let _ = tx.send(message);
+                }
+            }
+            None => {
+                shared.subscribers.lock().retain(|s| s.unbounded_send(message.clone()).is_ok());
+            }
+        }
+    }
+
+    // Fail any outstanding commands and end all event streams.
+    shared.pending.lock().clear();
+    shared.subscribers.lock().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn wss_is_supported() {
+        // The server closes the connection, so the TLS handshake fails. Without TLS
+        // support, the URL would be rejected before connecting.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("wss://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            drop(stream);
+        });
+
+        let err = WebSocketConnection::connect(&url).await.err().unwrap();
+        assert!(!err.to_string().contains("TLS support not compiled in"), "{}", err);
+    }
+}
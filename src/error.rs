@@ -21,6 +21,20 @@ pub enum WebDriverError {
     Timeout(String),
     #[error("webDriver command error: {0}")]
     CmdError(CmdError),
+    #[error("config error: {0}")]
+    ConfigError(String),
+    #[error("CDP error {code}: {message}")]
+    CdpError {
+        /// The CDP error code. See `extensions::chrome::cdp::error_code`.
+        code: i64,
+        message: String,
+    },
+    #[error("BiDi error {error}: {message}")]
+    BiDiError {
+        /// The BiDi error code, e.g. `no such frame`.
+        error: String,
+        message: String,
+    },
     #[error("{0}")]
     CustomError(String),
 }
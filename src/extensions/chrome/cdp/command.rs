@@ -0,0 +1,73 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A typed Chrome DevTools Protocol command.
+///
+/// The command struct is serialized as the command parameters, and the result is
+/// deserialized into `Returns`.
+///
+/// Use [`ChromeDevTools::execute_cdp_command()`](crate::extensions::chrome::ChromeDevTools::execute_cdp_command)
+/// to send a command.
+pub trait CdpCommand: Serialize {
+    /// The CDP method name, e.g. `Network.enable`.
+    const METHOD: &'static str;
+    /// The type returned by this command.
+    type Returns: DeserializeOwned;
+}
+
+/// A typed Chrome DevTools Protocol event.
+///
+/// Use [`CdpConnection::events_of()`](crate::extensions::chrome::cdp::CdpConnection::events_of)
+/// to receive events of this type.
+pub trait CdpEventType: DeserializeOwned {
+    /// The CDP event name, e.g. `Runtime.consoleAPICalled`.
+    const METHOD: &'static str;
+}
+
+/// The result of a CDP command that does not return anything.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Empty {}
+
+/// Error codes returned by the Chrome DevTools Protocol.
+///
+/// These are the JSON-RPC 2.0 error codes. Most errors from CDP domains use
+/// `SERVER_ERROR`.
+pub mod error_code {
+    pub const PARSE_ERROR: i64 = -32700;
+    pub const INVALID_REQUEST: i64 = -32600;
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    pub const INVALID_PARAMS: i64 = -32602;
+    pub const INTERNAL_ERROR: i64 = -32603;
+    pub const SERVER_ERROR: i64 = -32000;
+}
+
+#[derive(Debug, Deserialize)]
+struct CdpErrorBody {
+    code: i64,
+    message: String,
+}
+
+/// Extract the CDP error code and message from a chromedriver error message, such as
+/// `unknown error: unhandled inspector error: {"code":-32601,"message":"'Foo.bar' wasn't found"}`.
+pub(crate) fn parse_cdp_error(message: &str) -> Option<(i64, String)> {
+    let start = message.find('{')?;
+    let mut values = serde_json::Deserializer::from_str(&message[start..]).into_iter::<Value>();
+    let body: CdpErrorBody = serde_json::from_value(values.next()?.ok()?).ok()?;
+    Some((body.code, body.message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_chromedriver_error() {
+        let message = r#"unknown error: unhandled inspector error: {"code":-32601,"message":"'Foo.bar' wasn't found"}"#;
+        assert_eq!(
+            parse_cdp_error(message),
+            Some((error_code::METHOD_NOT_FOUND, "'Foo.bar' wasn't found".to_string()))
+        );
+        assert_eq!(parse_cdp_error("unknown error: something else"), None);
+    }
+}
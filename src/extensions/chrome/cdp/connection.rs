@@ -0,0 +1,342 @@
+use futures::channel::mpsc;
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::common::websocket::WebSocketConnection;
+use crate::error::{WebDriverError, WebDriverResult};
+use crate::extensions::chrome::cdp::{CdpCommand, CdpEventType};
+
+/// An event received over a CDP connection, such as `Network.requestWillBeSent`.
+#[derive(Debug, Clone)]
+pub struct CdpEvent {
+    /// The event name, e.g. `Runtime.consoleAPICalled`.
+    pub method: String,
+    pub params: Value,
+    /// The CDP session (attached target) that sent this event, if any.
+    pub session_id: Option<String>,
+}
+
+impl CdpEvent {
+    /// Deserialize the event parameters into the specified type.
+    pub fn params_as<T: DeserializeOwned>(&self) -> WebDriverResult<T> {
+        Ok(serde_json::from_value(self.params.clone())?)
+    }
+
+    /// Deserialize this event into the specified event type, or return `None` if it is
+    /// a different event.
+    pub fn parse<E: CdpEventType>(&self) -> Option<WebDriverResult<E>> {
+        (self.method == E::METHOD).then(|| self.params_as())
+    }
+}
+
+/// A stream of CDP events. The stream ends when the connection is closed.
+///
+/// See [`CdpConnection::events()`].
+pub struct CdpEventStream {
+    receiver: mpsc::UnboundedReceiver<Value>,
+    session_id: Option<String>,
+}
+
+impl Stream for CdpEventStream {
+    type Item = CdpEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let message = match self.receiver.poll_next_unpin(cx) {
+                Poll::Ready(Some(message)) => message,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+            let event = match serde_json::from_value::<RawEvent>(message) {
+                Ok(event) => event,
+                Err(_) => continue,
+            };
+            if self.session_id.is_some() && self.session_id != event.session_id {
+                continue;
+            }
+            return Poll::Ready(Some(CdpEvent {
+                method: event.method,
+                params: event.params,
+                session_id: event.session_id,
+            }));
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawEvent {
+    method: String,
+    #[serde(default)]
+    params: Value,
+    session_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawError {
+    code: i64,
+    message: String,
+}
+
+/// A Chrome DevTools Protocol client using a websocket connection to the browser.
+///
+/// Unlike [`ChromeDevTools`](crate::extensions::chrome::ChromeDevTools), which sends
+/// each command through chromedriver, this connection can also receive CDP events.
+/// Commands and responses are matched by id, so several commands can be in flight
+/// at the same time.
+///
+/// Clones share the same websocket. The connection is closed when the last clone
+/// is dropped.
+///
+/// # Example:
+/// ```ignore
+/// use futures::StreamExt;
+/// use thirtyfour::extensions::chrome::cdp::network;
+///
+/// let dev_tools = ChromeDevTools::new(driver.handle.clone());
+/// let cdp = dev_tools.connect().await?;
+/// let mut events = cdp.events();
+/// cdp.execute_cdp_command(&network::Enable::default()).await?;
+/// driver.get("https://example.com").await?;
+/// while let Some(event) = events.next().await {
+///     if event.method == "Network.requestWillBeSent" {
+///         println!("{}", event.params["request"]["url"]);
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CdpConnection {
+    connection: WebSocketConnection,
+    session_id: Option<String>,
+}
+
+impl CdpConnection {
+    /// Connect to the specified CDP websocket URL, e.g. the `webSocketDebuggerUrl`
+    /// from `http://localhost:9222/json/version` or the `se:cdp` capability.
+    pub async fn connect(url: &str) -> WebDriverResult<Self> {
+        Ok(Self {
+            connection: WebSocketConnection::connect(url).await?,
+            session_id: None,
+        })
+    }
+
+    /// Connect to the browser-level websocket for the specified debugger address
+    /// (e.g. `localhost:9222`), as returned by `/json/version`.
+    pub async fn connect_to_debugger_address(address: &str) -> WebDriverResult<Self> {
+        let url = fetch_websocket_url(address).await?;
+        Self::connect(&url).await
+    }
+
+    /// The CDP session id this connection sends commands to, if attached to a target.
+    pub fn session_id(&self) -> Option<&str> {
+        self.session_id.as_deref()
+    }
+
+    /// Attach to the specified target (usually a page), and return a connection that
+    /// sends commands to that target and only receives its events.
+    pub async fn attach_to_target(&self, target_id: &str) -> WebDriverResult<CdpConnection> {
+        let ret = self
+            .execute_cdp_with_params(
+                "Target.attachToTarget",
+                json!({"targetId": target_id, "flatten": true}),
+            )
+            .await?;
+        let session_id = ret["sessionId"].as_str().ok_or_else(|| {
+            WebDriverError::CustomError(format!("unable to attach to target {}", target_id))
+        })?;
+        Ok(Self {
+            connection: self.connection.clone(),
+            session_id: Some(session_id.to_string()),
+        })
+    }
+
+    /// Attach to the page with the specified target id, or to the first page if
+    /// there is no such target.
+    pub async fn attach_to_page(&self, target_id: Option<&str>) -> WebDriverResult<CdpConnection> {
+        let ret = self.execute_cdp("Target.getTargets").await?;
+        let targets = ret["targetInfos"].as_array().cloned().unwrap_or_default();
+        let id_of = |t: &Value| t["targetId"].as_str().map(String::from);
+        let target = targets
+            .iter()
+            .find(|t| target_id.is_some() && t["targetId"].as_str() == target_id)
+            .or_else(|| targets.iter().find(|t| t["type"] == "page"))
+            .and_then(id_of)
+            .ok_or_else(|| WebDriverError::CustomError("no page target found".to_string()))?;
+        self.attach_to_target(&target).await
+    }
+
+    /// Execute the specified command without parameters.
+    pub async fn execute_cdp(&self, cmd: &str) -> WebDriverResult<Value> {
+        self.execute_cdp_with_params(cmd, json!({})).await
+    }
+
+    /// Execute the specified command with the specified parameter(s).
+    ///
+    /// If the command fails in the browser, a `WebDriverError::CdpError` containing the
+    /// CDP error code is returned.
+    pub async fn execute_cdp_with_params(
+        &self,
+        cmd: &str,
+        cmd_args: Value,
+    ) -> WebDriverResult<Value> {
+        let mut message = json!({"method": cmd, "params": cmd_args});
+        if let Some(session_id) = &self.session_id {
+            message["sessionId"] = json!(session_id);
+        }
+
+        let mut response = self.connection.send(message).await?;
+        if let Some(error) = response.get("error") {
+            let error: RawError = serde_json::from_value(error.clone())?;
+            return Err(WebDriverError::CdpError {
+                code: error.code,
+                message: error.message,
+            });
+        }
+        Ok(match response["result"].take() {
+            Value::Null => json!({}),
+            v => v,
+        })
+    }
+
+    /// Execute the specified typed CDP command.
+    ///
+    /// See the [cdp](crate::extensions::chrome::cdp) module for the available commands.
+    pub async fn execute_cdp_command<C: CdpCommand>(
+        &self,
+        command: &C,
+    ) -> WebDriverResult<C::Returns> {
+        let params = serde_json::to_value(command)?;
+        let v = self.execute_cdp_with_params(C::METHOD, params).await?;
+        Ok(serde_json::from_value(v)?)
+    }
+
+    /// Subscribe to all events received from now on. For a connection that is attached
+    /// to a target, only that target's events are included.
+    ///
+    /// Most domains only send events once enabled, e.g. with `Network.enable`.
+    pub fn events(&self) -> CdpEventStream {
+        CdpEventStream {
+            receiver: self.connection.events(),
+            session_id: self.session_id.clone(),
+        }
+    }
+
+    /// Receive all events of the specified type from now on. Events that cannot be
+    /// deserialized are skipped.
+    pub fn events_of<E: CdpEventType + Send + 'static>(
+        &self,
+    ) -> impl Stream<Item = E> + Unpin + Send {
+        self.events().filter_map(|event| {
+            let parsed = match event.parse::<E>() {
+                Some(Ok(e)) => Some(e),
+                Some(Err(e)) => {
+                    log::warn!("unable to parse {} event: {}", E::METHOD, e);
+                    None
+                }
+                None => None,
+            };
+            futures::future::ready(parsed)
+        })
+    }
+}
+
+/// Get the browser-level websocket URL from the DevTools HTTP endpoint at the
+/// specified debugger address, e.g. `localhost:9222`.
+async fn fetch_websocket_url(address: &str) -> WebDriverResult<String> {
+    let uri: hyper::Uri = format!("http://{}/json/version", address)
+        .parse()
+        .map_err(|e| WebDriverError::CustomError(format!("invalid debugger address: {}", e)))?;
+    let http_error =
+        |e: hyper::Error| WebDriverError::CustomError(format!("unable to get {}: {}", uri, e));
+    let response = hyper::Client::new().get(uri.clone()).await.map_err(http_error)?;
+    let body = hyper::body::to_bytes(response.into_body()).await.map_err(http_error)?;
+
+    let version: Value = serde_json::from_slice(&body)?;
+    version["webSocketDebuggerUrl"].as_str().map(String::from).ok_or_else(|| {
+        WebDriverError::CustomError(format!("no webSocketDebuggerUrl found at {}", address))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::SinkExt;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::Message;
+
+    /// Accept a single connection, answer the first two commands in reverse order
+    /// and then send an event.
+    async fn stand_in(listener: TcpListener) {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let mut requests = Vec::new();
+        while requests.len() < 2 {
+            if let Some(Ok(Message::Text(text))) = ws.next().await {
+                requests.push(serde_json::from_str::<Value>(&text).unwrap());
+            }
+        }
+        for request in requests.iter().rev() {
+            let response = if request["method"] == "Fail.me" {
+                json!({"id": request["id"], "error": {"code": -32601, "message": "not found"}})
+            } else {
+                json!({"id": request["id"], "result": {"echo": request["params"]}})
+            };
+            ws.send(Message::Text(response.to_string())).await.unwrap();
+        }
+        let event = json!({"method": "Network.requestWillBeSent", "params": {"requestId": "1"}});
+        ws.send(Message::Text(event.to_string())).await.unwrap();
+        ws.close(None).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn responses_and_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(stand_in(listener));
+
+        let cdp = CdpConnection::connect(&url).await.unwrap();
+        let mut events = cdp.events();
+        let (ok, err) = tokio::join!(
+            cdp.execute_cdp_with_params("Echo.me", json!({"value": 1})),
+            cdp.execute_cdp("Fail.me")
+        );
+        assert_eq!(ok.unwrap(), json!({"echo": {"value": 1}}));
+        assert!(matches!(
+            err,
+            Err(WebDriverError::CdpError {
+                code: -32601,
+                ..
+            })
+        ));
+
+        let event = events.next().await.unwrap();
+        assert_eq!(event.method, "Network.requestWillBeSent");
+        assert_eq!(event.params["requestId"], "1");
+        assert!(events.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn websocket_url_from_json_version() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            use tokio::io::{AsyncReadExt, AsyncWriteExt};
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0; 1024];
+            let n = stream.read(&mut request).await.unwrap();
+            assert!(String::from_utf8_lossy(&request[..n]).starts_with("GET /json/version "));
+            let body = json!({"webSocketDebuggerUrl": "ws://localhost/devtools/browser/1"});
+            let body = body.to_string();
+            let response =
+                format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}", body.len(), body);
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let url = fetch_websocket_url(&address).await.unwrap();
+        assert_eq!(url, "ws://localhost/devtools/browser/1");
+    }
+}
@@ -0,0 +1,147 @@
+//! The CDP [Emulation](https://chromedevtools.github.io/devtools-protocol/tot/Emulation/) domain.
+
+use serde::Serialize;
+
+use super::{CdpCommand, Empty};
+
+/// Override the device screen dimensions, device scale factor and mobile flag.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetDeviceMetricsOverride {
+    /// Viewport width in pixels. 0 disables the override.
+    pub width: u32,
+    /// Viewport height in pixels. 0 disables the override.
+    pub height: u32,
+    /// 0 disables the override.
+    pub device_scale_factor: f64,
+    /// Emulate a mobile device, including the viewport meta tag and overlay scrollbars.
+    pub mobile: bool,
+}
+
+impl CdpCommand for SetDeviceMetricsOverride {
+    const METHOD: &'static str = "Emulation.setDeviceMetricsOverride";
+    type Returns = Empty;
+}
+
+/// Clear the device metrics override.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ClearDeviceMetricsOverride {}
+
+impl CdpCommand for ClearDeviceMetricsOverride {
+    const METHOD: &'static str = "Emulation.clearDeviceMetricsOverride";
+    type Returns = Empty;
+}
+
+/// Override the geolocation position. Omitting all fields emulates position unavailable.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SetGeolocationOverride {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latitude: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub longitude: Option<f64>,
+    /// Accuracy in metres.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accuracy: Option<f64>,
+}
+
+impl CdpCommand for SetGeolocationOverride {
+    const METHOD: &'static str = "Emulation.setGeolocationOverride";
+    type Returns = Empty;
+}
+
+/// Clear the geolocation override.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ClearGeolocationOverride {}
+
+impl CdpCommand for ClearGeolocationOverride {
+    const METHOD: &'static str = "Emulation.clearGeolocationOverride";
+    type Returns = Empty;
+}
+
+/// Override the timezone, e.g. `Europe/Berlin`. An empty string disables the override.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetTimezoneOverride {
+    pub timezone_id: String,
+}
+
+impl CdpCommand for SetTimezoneOverride {
+    const METHOD: &'static str = "Emulation.setTimezoneOverride";
+    type Returns = Empty;
+}
+
+/// Override the ICU locale, e.g. `de-DE`. `None` disables the override.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SetLocaleOverride {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+impl CdpCommand for SetLocaleOverride {
+    const METHOD: &'static str = "Emulation.setLocaleOverride";
+    type Returns = Empty;
+}
+
+/// Emulate the specified CSS media type and/or media features.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SetEmulatedMedia {
+    /// The media type to emulate, e.g. `print` or `screen`. An empty string disables
+    /// the override.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub features: Option<Vec<MediaFeature>>,
+}
+
+impl CdpCommand for SetEmulatedMedia {
+    const METHOD: &'static str = "Emulation.setEmulatedMedia";
+    type Returns = Empty;
+}
+
+/// A CSS media feature, e.g. `prefers-color-scheme` with value `dark`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MediaFeature {
+    pub name: String,
+    pub value: String,
+}
+
+impl MediaFeature {
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// Override the user agent.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetUserAgentOverride {
+    pub user_agent: String,
+    /// The browser language to emulate, e.g. `en-US`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_language: Option<String>,
+    /// The platform `navigator.platform` should return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+}
+
+impl CdpCommand for SetUserAgentOverride {
+    const METHOD: &'static str = "Emulation.setUserAgentOverride";
+    type Returns = Empty;
+}
+
+/// Enable or disable touch event emulation.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetTouchEmulationEnabled {
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_touch_points: Option<u32>,
+}
+
+impl CdpCommand for SetTouchEmulationEnabled {
+    const METHOD: &'static str = "Emulation.setTouchEmulationEnabled";
+    type Returns = Empty;
+}
@@ -0,0 +1,157 @@
+//! The CDP [Fetch](https://chromedevtools.github.io/devtools-protocol/tot/Fetch/) domain,
+//! which allows requests to be paused, modified, fulfilled or failed.
+
+use serde::{Deserialize, Serialize};
+
+use super::network::Request;
+use super::{CdpCommand, CdpEventType, Empty};
+
+/// The stage at which to pause requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RequestStage {
+    /// Before the request is sent.
+    Request,
+    /// After the response headers are received.
+    Response,
+}
+
+/// Requests matching this pattern are paused.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestPattern {
+    /// A URL pattern, where `*` matches zero or more characters and `?` matches one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url_pattern: Option<String>,
+    /// e.g. `Document`, `XHR`, `Fetch` or `Image`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_stage: Option<RequestStage>,
+}
+
+/// Enable request interception. Matching requests are paused and reported with
+/// `Fetch.requestPaused`, and must then be continued, fulfilled or failed.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Enable {
+    /// Pause all requests if this is `None`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patterns: Option<Vec<RequestPattern>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handle_auth_requests: Option<bool>,
+}
+
+impl CdpCommand for Enable {
+    const METHOD: &'static str = "Fetch.enable";
+    type Returns = Empty;
+}
+
+/// Disable request interception.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Disable {}
+
+impl CdpCommand for Disable {
+    const METHOD: &'static str = "Fetch.disable";
+    type Returns = Empty;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeaderEntry {
+    pub name: String,
+    pub value: String,
+}
+
+/// Continue a paused request, optionally modifying it.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContinueRequest {
+    pub request_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// The base64-encoded request body.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Vec<HeaderEntry>>,
+}
+
+impl CdpCommand for ContinueRequest {
+    const METHOD: &'static str = "Fetch.continueRequest";
+    type Returns = Empty;
+}
+
+/// Fulfill a paused request with the specified response.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FulfillRequest {
+    pub request_id: String,
+    pub response_code: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_headers: Option<Vec<HeaderEntry>>,
+    /// The base64-encoded response body.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_phrase: Option<String>,
+}
+
+impl CdpCommand for FulfillRequest {
+    const METHOD: &'static str = "Fetch.fulfillRequest";
+    type Returns = Empty;
+}
+
+/// The network error to fail a request with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErrorReason {
+    Failed,
+    Aborted,
+    TimedOut,
+    AccessDenied,
+    ConnectionClosed,
+    ConnectionReset,
+    ConnectionRefused,
+    ConnectionAborted,
+    ConnectionFailed,
+    NameNotResolved,
+    InternetDisconnected,
+    AddressUnreachable,
+    BlockedByClient,
+    BlockedByResponse,
+}
+
+/// Fail a paused request with a network error.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailRequest {
+    pub request_id: String,
+    pub error_reason: ErrorReason,
+}
+
+impl CdpCommand for FailRequest {
+    const METHOD: &'static str = "Fetch.failRequest";
+    type Returns = Empty;
+}
+
+/// Sent when a request matching one of the patterns is paused.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestPaused {
+    /// The id to use with `ContinueRequest`, `FulfillRequest` and `FailRequest`.
+    pub request_id: String,
+    pub request: Request,
+    pub resource_type: String,
+    /// Only set when paused at the `Response` stage.
+    #[serde(default)]
+    pub response_status_code: Option<u16>,
+    #[serde(default)]
+    pub response_headers: Option<Vec<HeaderEntry>>,
+    /// The id of the request in `Network` events, if network tracking is enabled.
+    #[serde(default)]
+    pub network_id: Option<String>,
+}
+
+impl CdpEventType for RequestPaused {
+    const METHOD: &'static str = "Fetch.requestPaused";
+}
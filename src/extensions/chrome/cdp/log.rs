@@ -0,0 +1,59 @@
+//! The CDP [Log](https://chromedevtools.github.io/devtools-protocol/tot/Log/) domain.
+
+use serde::{Deserialize, Serialize};
+
+use super::{CdpCommand, CdpEventType, Empty};
+
+/// Enable the log domain. Existing entries are sent as `Log.entryAdded` events.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Enable {}
+
+impl CdpCommand for Enable {
+    const METHOD: &'static str = "Log.enable";
+    type Returns = Empty;
+}
+
+/// Disable the log domain.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Disable {}
+
+impl CdpCommand for Disable {
+    const METHOD: &'static str = "Log.disable";
+    type Returns = Empty;
+}
+
+/// Clear the log.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Clear {}
+
+impl CdpCommand for Clear {
+    const METHOD: &'static str = "Log.clear";
+    type Returns = Empty;
+}
+
+/// A browser log entry, such as a failed network request or a deprecation warning.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    /// Where the entry came from, e.g. `network`, `violation` or `javascript`.
+    pub source: String,
+    /// One of `verbose`, `info`, `warning` or `error`.
+    pub level: String,
+    pub text: String,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: f64,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub line_number: Option<i64>,
+}
+
+/// Sent when a new log entry is added.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EntryAdded {
+    pub entry: LogEntry,
+}
+
+impl CdpEventType for EntryAdded {
+    const METHOD: &'static str = "Log.entryAdded";
+}
@@ -0,0 +1,305 @@
+//! The CDP [Network](https://chromedevtools.github.io/devtools-protocol/tot/Network/) domain.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{CdpCommand, CdpEventType, Empty};
+
+/// Enable network tracking. Network events will now be delivered to the client.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Enable {
+    /// Buffer size in bytes to use when preserving network payloads (XHRs, etc).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_total_buffer_size: Option<u64>,
+    /// Per-resource buffer size in bytes to use when preserving network payloads.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_resource_buffer_size: Option<u64>,
+    /// Longest post body size (in bytes) that would be included in `requestWillBeSent`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_post_data_size: Option<u64>,
+}
+
+impl CdpCommand for Enable {
+    const METHOD: &'static str = "Network.enable";
+    type Returns = Empty;
+}
+
+/// Disable network tracking.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Disable {}
+
+impl CdpCommand for Disable {
+    const METHOD: &'static str = "Network.disable";
+    type Returns = Empty;
+}
+
+/// Clear the browser cache.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ClearBrowserCache {}
+
+impl CdpCommand for ClearBrowserCache {
+    const METHOD: &'static str = "Network.clearBrowserCache";
+    type Returns = Empty;
+}
+
+/// Clear all browser cookies.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ClearBrowserCookies {}
+
+impl CdpCommand for ClearBrowserCookies {
+    const METHOD: &'static str = "Network.clearBrowserCookies";
+    type Returns = Empty;
+}
+
+/// Toggle ignoring the cache for each request.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetCacheDisabled {
+    pub cache_disabled: bool,
+}
+
+impl CdpCommand for SetCacheDisabled {
+    const METHOD: &'static str = "Network.setCacheDisabled";
+    type Returns = Empty;
+}
+
+/// Send the specified extra HTTP headers with each request.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SetExtraHttpHeaders {
+    pub headers: HashMap<String, String>,
+}
+
+impl CdpCommand for SetExtraHttpHeaders {
+    const METHOD: &'static str = "Network.setExtraHTTPHeaders";
+    type Returns = Empty;
+}
+
+/// Block requests to URLs matching any of the specified patterns. Wildcards (`*`) are allowed.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SetBlockedUrls {
+    pub urls: Vec<String>,
+}
+
+impl CdpCommand for SetBlockedUrls {
+    const METHOD: &'static str = "Network.setBlockedURLs";
+    type Returns = Empty;
+}
+
+/// Override the user agent.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetUserAgentOverride {
+    pub user_agent: String,
+    /// The browser language to emulate, e.g. `en-US`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_language: Option<String>,
+    /// The platform `navigator.platform` should return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+}
+
+impl CdpCommand for SetUserAgentOverride {
+    const METHOD: &'static str = "Network.setUserAgentOverride";
+    type Returns = Empty;
+}
+
+/// Emulate network conditions. Throughput is in bytes per second, and -1 disables
+/// throttling.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmulateNetworkConditions {
+    pub offline: bool,
+    /// Minimum latency from request sent to response headers received, in milliseconds.
+    pub latency: f64,
+    pub download_throughput: f64,
+    pub upload_throughput: f64,
+    /// One of `none`, `cellular2g`, `cellular3g`, `cellular4g`, `bluetooth`, `ethernet`,
+    /// `wifi`, `wimax` or `other`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection_type: Option<String>,
+}
+
+impl CdpCommand for EmulateNetworkConditions {
+    const METHOD: &'static str = "Network.emulateNetworkConditions";
+    type Returns = Empty;
+}
+
+/// Get the cookies for the current URL, or for the specified URLs.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GetCookies {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub urls: Option<Vec<String>>,
+}
+
+impl CdpCommand for GetCookies {
+    const METHOD: &'static str = "Network.getCookies";
+    type Returns = GetCookiesReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GetCookiesReturns {
+    pub cookies: Vec<Cookie>,
+}
+
+/// A browser cookie.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    pub domain: String,
+    pub path: String,
+    /// Expiry date, in seconds since the UNIX epoch. -1 for session cookies.
+    pub expires: f64,
+    pub size: u64,
+    pub http_only: bool,
+    pub secure: bool,
+    pub session: bool,
+    /// One of `Strict`, `Lax` or `None`.
+    #[serde(default)]
+    pub same_site: Option<String>,
+}
+
+/// Get the body of the response to the specified request.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetResponseBody {
+    pub request_id: String,
+}
+
+impl CdpCommand for GetResponseBody {
+    const METHOD: &'static str = "Network.getResponseBody";
+    type Returns = GetResponseBodyReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetResponseBodyReturns {
+    pub body: String,
+    /// True if the body is base64-encoded.
+    pub base64_encoded: bool,
+}
+
+/// An HTTP request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub url: String,
+    pub method: String,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// The request body, if it is not too large.
+    #[serde(default)]
+    pub post_data: Option<String>,
+    #[serde(default)]
+    pub has_post_data: Option<bool>,
+}
+
+/// Timing information for a request, in milliseconds relative to `request_time`.
+/// Values are -1 if not applicable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceTiming {
+    /// The start of the request, in seconds since an arbitrary point in the past.
+    pub request_time: f64,
+    pub dns_start: f64,
+    pub dns_end: f64,
+    pub connect_start: f64,
+    pub connect_end: f64,
+    pub ssl_start: f64,
+    pub ssl_end: f64,
+    pub send_start: f64,
+    pub send_end: f64,
+    pub receive_headers_end: f64,
+}
+
+/// An HTTP response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    pub url: String,
+    pub status: u16,
+    pub status_text: String,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    pub mime_type: String,
+    /// The protocol, e.g. `http/1.1` or `h2`.
+    #[serde(default)]
+    pub protocol: Option<String>,
+    #[serde(default, rename = "remoteIPAddress")]
+    pub remote_ip_address: Option<String>,
+    /// The number of bytes received so far.
+    pub encoded_data_length: f64,
+    #[serde(default)]
+    pub timing: Option<ResourceTiming>,
+    #[serde(default)]
+    pub from_disk_cache: Option<bool>,
+}
+
+/// Sent when a request is about to be sent.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestWillBeSent {
+    pub request_id: String,
+    pub request: Request,
+    /// Seconds since an arbitrary point in the past.
+    pub timestamp: f64,
+    /// Seconds since the Unix epoch.
+    pub wall_time: f64,
+    /// The resource type, e.g. `Document`, `XHR` or `Image`.
+    #[serde(default, rename = "type")]
+    pub resource_type: Option<String>,
+    /// Set if this request is a redirect. The response is for the previous request
+    /// with the same `request_id`.
+    #[serde(default)]
+    pub redirect_response: Option<Response>,
+}
+
+impl CdpEventType for RequestWillBeSent {
+    const METHOD: &'static str = "Network.requestWillBeSent";
+}
+
+/// Sent when the response headers are received.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseReceived {
+    pub request_id: String,
+    pub timestamp: f64,
+    #[serde(default, rename = "type")]
+    pub resource_type: Option<String>,
+    pub response: Response,
+}
+
+impl CdpEventType for ResponseReceived {
+    const METHOD: &'static str = "Network.responseReceived";
+}
+
+/// Sent when a request has finished loading.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadingFinished {
+    pub request_id: String,
+    pub timestamp: f64,
+    /// The total number of bytes received for this request.
+    pub encoded_data_length: f64,
+}
+
+impl CdpEventType for LoadingFinished {
+    const METHOD: &'static str = "Network.loadingFinished";
+}
+
+/// Sent when a request has failed to load.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadingFailed {
+    pub request_id: String,
+    pub timestamp: f64,
+    pub error_text: String,
+    #[serde(default)]
+    pub canceled: Option<bool>,
+}
+
+impl CdpEventType for LoadingFailed {
+    const METHOD: &'static str = "Network.loadingFailed";
+}
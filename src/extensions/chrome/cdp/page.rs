@@ -0,0 +1,163 @@
+//! The CDP [Page](https://chromedevtools.github.io/devtools-protocol/tot/Page/) domain.
+
+use serde::{Deserialize, Serialize};
+
+use super::{CdpCommand, Empty};
+
+/// Enable page domain notifications.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Enable {}
+
+impl CdpCommand for Enable {
+    const METHOD: &'static str = "Page.enable";
+    type Returns = Empty;
+}
+
+/// Disable page domain notifications.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Disable {}
+
+impl CdpCommand for Disable {
+    const METHOD: &'static str = "Page.disable";
+    type Returns = Empty;
+}
+
+/// Navigate the current page to the specified URL.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Navigate {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referrer: Option<String>,
+}
+
+impl CdpCommand for Navigate {
+    const METHOD: &'static str = "Page.navigate";
+    type Returns = NavigateReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NavigateReturns {
+    pub frame_id: String,
+    #[serde(default)]
+    pub loader_id: Option<String>,
+    /// Set if the navigation failed, e.g. `net::ERR_NAME_NOT_RESOLVED`.
+    #[serde(default)]
+    pub error_text: Option<String>,
+}
+
+/// Reload the current page.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reload {
+    /// If true, the browser cache is ignored (as if the user pressed Shift+Refresh).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_cache: Option<bool>,
+    /// Script to be injected into all frames of the page after reload.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script_to_evaluate_on_load: Option<String>,
+}
+
+impl CdpCommand for Reload {
+    const METHOD: &'static str = "Page.reload";
+    type Returns = Empty;
+}
+
+/// Bring the page to the front (activate the tab).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BringToFront {}
+
+impl CdpCommand for BringToFront {
+    const METHOD: &'static str = "Page.bringToFront";
+    type Returns = Empty;
+}
+
+/// Capture a screenshot of the page.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureScreenshot {
+    /// One of `jpeg`, `png` or `webp`. Defaults to `png`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Compression quality from 0 to 100 (jpeg and webp only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<u32>,
+    /// Capture the whole page rather than just the viewport.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capture_beyond_viewport: Option<bool>,
+}
+
+impl CdpCommand for CaptureScreenshot {
+    const METHOD: &'static str = "Page.captureScreenshot";
+    type Returns = CaptureScreenshotReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CaptureScreenshotReturns {
+    /// Base64-encoded image data.
+    pub data: String,
+}
+
+/// Evaluate the specified script in every frame upon creation, before any of the
+/// frame's own scripts.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AddScriptToEvaluateOnNewDocument {
+    pub source: String,
+}
+
+impl CdpCommand for AddScriptToEvaluateOnNewDocument {
+    const METHOD: &'static str = "Page.addScriptToEvaluateOnNewDocument";
+    type Returns = AddScriptToEvaluateOnNewDocumentReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AddScriptToEvaluateOnNewDocumentReturns {
+    /// Identifier of the added script, for use with `RemoveScriptToEvaluateOnNewDocument`.
+    pub identifier: String,
+}
+
+/// Remove a script that was added with `AddScriptToEvaluateOnNewDocument`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RemoveScriptToEvaluateOnNewDocument {
+    pub identifier: String,
+}
+
+impl CdpCommand for RemoveScriptToEvaluateOnNewDocument {
+    const METHOD: &'static str = "Page.removeScriptToEvaluateOnNewDocument";
+    type Returns = Empty;
+}
+
+/// Get the layout metrics of the page, such as the viewport and content size.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GetLayoutMetrics {}
+
+impl CdpCommand for GetLayoutMetrics {
+    const METHOD: &'static str = "Page.getLayoutMetrics";
+    type Returns = GetLayoutMetricsReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLayoutMetricsReturns {
+    pub css_layout_viewport: LayoutViewport,
+    pub css_content_size: ContentSize,
+}
+
+/// The layout viewport position and size, in CSS pixels.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutViewport {
+    pub page_x: f64,
+    pub page_y: f64,
+    pub client_width: f64,
+    pub client_height: f64,
+}
+
+/// The size of the page content, in CSS pixels.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContentSize {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
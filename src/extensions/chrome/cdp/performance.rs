@@ -0,0 +1,49 @@
+//! The CDP [Performance](https://chromedevtools.github.io/devtools-protocol/tot/Performance/) domain.
+
+use serde::{Deserialize, Serialize};
+
+use super::{CdpCommand, Empty};
+
+/// Enable collecting and reporting metrics.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Enable {
+    /// Either `timeTicks` (the default) or `threadTicks`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_domain: Option<String>,
+}
+
+impl CdpCommand for Enable {
+    const METHOD: &'static str = "Performance.enable";
+    type Returns = Empty;
+}
+
+/// Disable collecting and reporting metrics.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Disable {}
+
+impl CdpCommand for Disable {
+    const METHOD: &'static str = "Performance.disable";
+    type Returns = Empty;
+}
+
+/// Get the current values of the runtime metrics. Metrics must be enabled first.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GetMetrics {}
+
+impl CdpCommand for GetMetrics {
+    const METHOD: &'static str = "Performance.getMetrics";
+    type Returns = GetMetricsReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GetMetricsReturns {
+    pub metrics: Vec<Metric>,
+}
+
+/// A single runtime metric, e.g. `JSHeapUsedSize` or `LayoutCount`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Metric {
+    pub name: String,
+    pub value: f64,
+}
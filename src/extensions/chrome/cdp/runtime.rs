@@ -0,0 +1,162 @@
+//! The CDP [Runtime](https://chromedevtools.github.io/devtools-protocol/tot/Runtime/) domain.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{CdpCommand, CdpEventType, Empty};
+
+/// Enable reporting of execution contexts and console API calls.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Enable {}
+
+impl CdpCommand for Enable {
+    const METHOD: &'static str = "Runtime.enable";
+    type Returns = Empty;
+}
+
+/// Disable reporting of execution contexts and console API calls.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Disable {}
+
+impl CdpCommand for Disable {
+    const METHOD: &'static str = "Runtime.disable";
+    type Returns = Empty;
+}
+
+/// Evaluate an expression in the global context of the page.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Evaluate {
+    pub expression: String,
+    /// Return the result as a JSON value rather than a reference to the object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_by_value: Option<bool>,
+    /// Wait for the result if it is a promise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub await_promise: Option<bool>,
+}
+
+impl Evaluate {
+    /// Evaluate the expression, returning the result by value and awaiting promises.
+    pub fn new(expression: impl Into<String>) -> Self {
+        Self {
+            expression: expression.into(),
+            return_by_value: Some(true),
+            await_promise: Some(true),
+        }
+    }
+}
+
+impl CdpCommand for Evaluate {
+    const METHOD: &'static str = "Runtime.evaluate";
+    type Returns = EvaluateReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvaluateReturns {
+    pub result: RemoteObject,
+    /// Set if the expression threw an exception.
+    #[serde(default)]
+    pub exception_details: Option<ExceptionDetails>,
+}
+
+/// A mirror object referencing the original JavaScript object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteObject {
+    /// The object type, e.g. `object`, `string` or `undefined`.
+    #[serde(rename = "type")]
+    pub object_type: String,
+    /// The object subtype, e.g. `array`, `null` or `error`.
+    #[serde(default)]
+    pub subtype: Option<String>,
+    /// The value, for primitives or when returned by value.
+    #[serde(default)]
+    pub value: Option<Value>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Unique object identifier, for non-primitive values not returned by value.
+    #[serde(default)]
+    pub object_id: Option<String>,
+}
+
+/// Detailed information about an exception (or error) that was thrown.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExceptionDetails {
+    pub exception_id: i64,
+    pub text: String,
+    pub line_number: i64,
+    pub column_number: i64,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub exception: Option<RemoteObject>,
+}
+
+/// Get the JavaScript heap usage.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GetHeapUsage {}
+
+impl CdpCommand for GetHeapUsage {
+    const METHOD: &'static str = "Runtime.getHeapUsage";
+    type Returns = GetHeapUsageReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetHeapUsageReturns {
+    /// Used heap size in bytes.
+    pub used_size: f64,
+    /// Allocated heap size in bytes.
+    pub total_size: f64,
+}
+
+/// A JavaScript stack frame.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    pub function_name: String,
+    pub url: String,
+    /// Zero-based line number.
+    pub line_number: i64,
+    pub column_number: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackTrace {
+    pub call_frames: Vec<CallFrame>,
+}
+
+/// Sent when a console API (e.g. `console.log`) is called.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsoleApiCalled {
+    /// The console method, e.g. `log`, `warning` or `error`.
+    #[serde(rename = "type")]
+    pub call_type: String,
+    pub args: Vec<RemoteObject>,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: f64,
+    #[serde(default)]
+    pub stack_trace: Option<StackTrace>,
+}
+
+impl CdpEventType for ConsoleApiCalled {
+    const METHOD: &'static str = "Runtime.consoleAPICalled";
+}
+
+/// Sent when an exception is not handled.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExceptionThrown {
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: f64,
+    pub exception_details: ExceptionDetails,
+}
+
+impl CdpEventType for ExceptionThrown {
+    const METHOD: &'static str = "Runtime.exceptionThrown";
+}
@@ -0,0 +1,34 @@
+//! The CDP [Security](https://chromedevtools.github.io/devtools-protocol/tot/Security/) domain.
+
+use serde::Serialize;
+
+use super::{CdpCommand, Empty};
+
+/// Enable tracking of security state changes.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Enable {}
+
+impl CdpCommand for Enable {
+    const METHOD: &'static str = "Security.enable";
+    type Returns = Empty;
+}
+
+/// Disable tracking of security state changes.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Disable {}
+
+impl CdpCommand for Disable {
+    const METHOD: &'static str = "Security.disable";
+    type Returns = Empty;
+}
+
+/// Enable or disable ignoring all certificate errors.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SetIgnoreCertificateErrors {
+    pub ignore: bool,
+}
+
+impl CdpCommand for SetIgnoreCertificateErrors {
+    const METHOD: &'static str = "Security.setIgnoreCertificateErrors";
+    type Returns = Empty;
+}
@@ -0,0 +1,77 @@
+//! The CDP [Storage](https://chromedevtools.github.io/devtools-protocol/tot/Storage/) domain.
+
+use serde::{Deserialize, Serialize};
+
+use super::network::Cookie;
+use super::{CdpCommand, Empty};
+
+/// Clear storage for the specified origin.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearDataForOrigin {
+    /// The origin, e.g. `https://example.com`.
+    pub origin: String,
+    /// Comma-separated list of storage types to clear, e.g. `cookies,local_storage`,
+    /// or `all`.
+    pub storage_types: String,
+}
+
+impl CdpCommand for ClearDataForOrigin {
+    const METHOD: &'static str = "Storage.clearDataForOrigin";
+    type Returns = Empty;
+}
+
+/// Get the storage usage and quota for the specified origin.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GetUsageAndQuota {
+    pub origin: String,
+}
+
+impl CdpCommand for GetUsageAndQuota {
+    const METHOD: &'static str = "Storage.getUsageAndQuota";
+    type Returns = GetUsageAndQuotaReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetUsageAndQuotaReturns {
+    /// Storage usage in bytes.
+    pub usage: f64,
+    /// Storage quota in bytes.
+    pub quota: f64,
+    #[serde(default)]
+    pub override_active: bool,
+    #[serde(default)]
+    pub usage_breakdown: Vec<UsageForType>,
+}
+
+/// Storage usage for a single storage type.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageForType {
+    pub storage_type: String,
+    pub usage: f64,
+}
+
+/// Get all browser cookies.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GetCookies {}
+
+impl CdpCommand for GetCookies {
+    const METHOD: &'static str = "Storage.getCookies";
+    type Returns = GetCookiesReturns;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GetCookiesReturns {
+    pub cookies: Vec<Cookie>,
+}
+
+/// Clear all browser cookies.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ClearCookies {}
+
+impl CdpCommand for ClearCookies {
+    const METHOD: &'static str = "Storage.clearCookies";
+    type Returns = Empty;
+}
@@ -9,6 +9,7 @@ pub enum ChromeCommand {
     LaunchApp(String),
     GetNetworkConditions,
     SetNetworkConditions(NetworkConditions),
+    DeleteNetworkConditions,
     ExecuteCdpCommand(String, Value),
     GetSinks,
     GetIssueMessage,
@@ -22,9 +23,9 @@ impl WebDriverCompatibleCommand for ChromeCommand {
         let base = { base_url.join(&format!("session/{}/", session_id.as_ref().unwrap()))? };
         match &self {
             ChromeCommand::LaunchApp(_) => base.join("chromium/launch_app"),
-            ChromeCommand::GetNetworkConditions | ChromeCommand::SetNetworkConditions(_) => {
-                base.join("chromium/network_conditions")
-            }
+            ChromeCommand::GetNetworkConditions
+            | ChromeCommand::SetNetworkConditions(_)
+            | ChromeCommand::DeleteNetworkConditions => base.join("chromium/network_conditions"),
             ChromeCommand::ExecuteCdpCommand(..) => base.join("goog/cdp/execute"),
             ChromeCommand::GetSinks => base.join("goog/cast/get_sinks"),
             ChromeCommand::GetIssueMessage => base.join("goog/cast/get_issue_message"),
@@ -47,6 +48,9 @@ impl WebDriverCompatibleCommand for ChromeCommand {
                 method = Method::POST;
                 body = Some(json!({ "network_conditions": conditions }).to_string())
             }
+            ChromeCommand::DeleteNetworkConditions => {
+                method = Method::DELETE;
+            }
             ChromeCommand::ExecuteCdpCommand(command, params) => {
                 method = Method::POST;
                 body = Some(json!({"cmd": command, "params": params }).to_string())
@@ -1,9 +1,16 @@
 use serde_json::{json, Value};
+use std::future::Future;
 
-use crate::error::WebDriverResult;
+use fantoccini::error::CmdError;
+use fantoccini::wd::Capabilities;
+
+use crate::error::{WebDriverError, WebDriverResult};
+use crate::extensions::chrome::cdp::{network, parse_cdp_error, CdpCommand, CdpConnection};
 use crate::extensions::chrome::networkconditions::NetworkConditions;
 use crate::extensions::chrome::ChromeCommand;
 use crate::session::handle::SessionHandle;
+use crate::session::interception::RequestInterceptor;
+use crate::ChromeCapabilities;
 
 /// The ChromeDevTools struct allows you to interact with Chromium-based browsers via
 /// the Chrome Devtools Protocol (CDP).
@@ -144,6 +151,86 @@ impl ChromeDevTools {
         Ok(())
     }
 
+    /// Remove any network conditions set with `set_network_conditions()`.
+    pub async fn delete_network_conditions(&self) -> WebDriverResult<()> {
+        self.handle.client.issue_cmd(ChromeCommand::DeleteNetworkConditions).await?;
+        Ok(())
+    }
+
+    /// Run the specified future with the specified network conditions, then restore the
+    /// previous conditions (or remove them if none were set).
+    ///
+    /// The conditions are restored even if the future returns an error. The error from
+    /// the future takes precedence over any error restoring the conditions.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// use thirtyfour::extensions::chrome::{ChromeDevTools, NetworkConditions};
+    ///
+    /// let dev_tools = ChromeDevTools::new(driver.handle.clone());
+    /// dev_tools
+    ///     .with_network_conditions(&NetworkConditions::offline(), async {
+    ///         driver.refresh().await?;
+    ///         driver.query(By::Id("offline-banner")).first().await?;
+    ///         Ok(())
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn with_network_conditions<F, T>(
+        &self,
+        conditions: &NetworkConditions,
+        f: F,
+    ) -> WebDriverResult<T>
+    where
+        F: Future<Output = WebDriverResult<T>>,
+    {
+        let previous = match self.get_network_conditions().await {
+            Ok(previous) => Some(previous),
+            Err(e) if is_no_network_conditions_error(&e) => None,
+            Err(e) => return Err(e),
+        };
+        self.set_network_conditions(conditions).await?;
+        let result = f.await;
+        let restored = match previous {
+            Some(previous) => self.set_network_conditions(&previous).await,
+            None => self.delete_network_conditions().await,
+        };
+        let value = result?;
+        restored?;
+        Ok(value)
+    }
+
+    /// Block requests to the specified origins, while requests to other origins are
+    /// unaffected. Pass an empty slice to clear.
+    ///
+    /// Blocked requests fail with `net::ERR_BLOCKED_BY_CLIENT`, which is not the same as
+    /// being offline: `navigator.onLine` stays true and the error type differs. To
+    /// emulate a lost connection for the whole page, use `NetworkConditions::offline()`.
+    ///
+    /// Each origin is matched as a URL prefix, e.g. `https://api.example.com`.
+    /// This replaces any URLs previously blocked via `Network.setBlockedURLs`.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let dev_tools = ChromeDevTools::new(driver.handle.clone());
+    /// dev_tools.set_blocked_origins(&["https://api.example.com"]).await?;
+    /// driver.refresh().await?;
+    /// // ...
+    /// dev_tools.set_blocked_origins(&[]).await?;
+    /// ```
+    pub async fn set_blocked_origins(&self, origins: &[&str]) -> WebDriverResult<()> {
+        if !origins.is_empty() {
+            self.execute_cdp_command(&network::Enable::default()).await?;
+        }
+        let urls =
+            origins.iter().map(|origin| format!("{}/*", origin.trim_end_matches('/'))).collect();
+        self.execute_cdp_command(&network::SetBlockedUrls {
+            urls,
+        })
+        .await?;
+        Ok(())
+    }
+
     /// Execute the specified command without parameters.
     /// For commands that require parameters, use `execute_cdp_with_params()` instead.
     ///
@@ -199,6 +286,9 @@ impl ChromeDevTools {
     /// #     })
     /// # }
     /// ```
+    ///
+    /// If the command fails in the browser, a `WebDriverError::CdpError` containing the
+    /// CDP error code is returned.
     pub async fn execute_cdp_with_params(
         &self,
         cmd: &str,
@@ -208,10 +298,104 @@ impl ChromeDevTools {
             .handle
             .client
             .issue_cmd(ChromeCommand::ExecuteCdpCommand(cmd.to_string(), cmd_args))
-            .await?;
+            .await
+            .map_err(|e| match &e {
+                CmdError::Standard(wd) => match parse_cdp_error(&wd.message) {
+                    Some((code, message)) => WebDriverError::CdpError {
+                        code,
+                        message,
+                    },
+                    None => e.into(),
+                },
+                _ => e.into(),
+            })?;
         Ok(v)
     }
 
+    /// Execute the specified typed CDP command.
+    ///
+    /// See the [cdp](crate::extensions::chrome::cdp) module for the available commands.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// use thirtyfour::extensions::chrome::cdp::{network, runtime};
+    ///
+    /// let dev_tools = ChromeDevTools::new(driver.handle.clone());
+    /// dev_tools.execute_cdp_command(&network::SetCacheDisabled { cache_disabled: true }).await?;
+    /// let ret = dev_tools.execute_cdp_command(&runtime::Evaluate::new("1 + 2")).await?;
+    /// assert_eq!(ret.result.value, Some(serde_json::json!(3)));
+    /// ```
+    pub async fn execute_cdp_command<C: CdpCommand>(
+        &self,
+        command: &C,
+    ) -> WebDriverResult<C::Returns> {
+        let params = serde_json::to_value(command)?;
+        let v = match self.execute_cdp_with_params(C::METHOD, params).await? {
+            Value::Null => json!({}),
+            v => v,
+        };
+        Ok(serde_json::from_value(v)?)
+    }
+
+    /// Open a websocket connection to the browser's DevTools, attached to the current
+    /// window. Unlike the other methods here, the connection can receive CDP events.
+    ///
+    /// The websocket URL is taken from the `se:cdp` capability (set by Selenium Grid)
+    /// if present, and otherwise from the `debuggerAddress` in `goog:chromeOptions` (or
+    /// `ms:edgeOptions` for Edge).
+    /// The debugger address must be reachable from the machine running the tests.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// use futures::StreamExt;
+    ///
+    /// let dev_tools = ChromeDevTools::new(driver.handle.clone());
+    /// let cdp = dev_tools.connect().await?;
+    /// let mut events = cdp.events();
+    /// cdp.execute_cdp("Runtime.enable").await?;
+    /// while let Some(event) = events.next().await {
+    ///     if event.method == "Runtime.consoleAPICalled" {
+    ///         println!("{:?}", event.params["args"]);
+    ///     }
+    /// }
+    /// ```
+    pub async fn connect(&self) -> WebDriverResult<CdpConnection> {
+        let cdp = match self.websocket_url().await? {
+            WebSocketUrl::Cdp(url) => CdpConnection::connect(&url).await?,
+            WebSocketUrl::DebuggerAddress(address) => {
+                CdpConnection::connect_to_debugger_address(&address).await?
+            }
+        };
+        let window: String = self.handle.current_window_handle().await?.into();
+        cdp.attach_to_page(Some(&window)).await
+    }
+
+    /// Start intercepting network requests via the CDP `Fetch` domain.
+    ///
+    /// See [`RequestInterceptor`] for details.
+    pub async fn intercept_requests(&self) -> WebDriverResult<RequestInterceptor> {
+        RequestInterceptor::with_cdp(self.connect().await?).await
+    }
+
+    /// Find the CDP websocket URL or debugger address, checking the requested
+    /// capabilities first and then those of the running session.
+    async fn websocket_url(&self) -> WebDriverResult<WebSocketUrl> {
+        if let Some(url) = websocket_url_from_caps(self.handle.config.get_capabilities()) {
+            return Ok(url);
+        }
+        let session = match self.handle.session_capabilities().await? {
+            Value::Object(caps) => caps,
+            _ => Capabilities::new(),
+        };
+        websocket_url_from_caps(session).ok_or_else(|| {
+            WebDriverError::CustomError(
+                "no se:cdp capability or debuggerAddress in goog:chromeOptions or ms:edgeOptions \
+                 found for this session"
+                    .to_string(),
+            )
+        })
+    }
+
     /// Get the list of sinks available for cast.
     pub async fn get_sinks(&self) -> WebDriverResult<Value> {
         let v = self.handle.client.issue_cmd(ChromeCommand::GetSinks).await?;
@@ -245,3 +429,112 @@ impl ChromeDevTools {
         Ok(())
     }
 }
+
+/// Return true if the error is chromedriver's response to getting the network conditions
+/// before any have been set.
+fn is_no_network_conditions_error(e: &WebDriverError) -> bool {
+    e.to_string().contains("network conditions must be set")
+}
+
+#[derive(Debug, PartialEq)]
+enum WebSocketUrl {
+    Cdp(String),
+    DebuggerAddress(String),
+}
+
+/// Read the CDP websocket URL or debugger address from the specified capabilities.
+fn websocket_url_from_caps(caps: Capabilities) -> Option<WebSocketUrl> {
+    if let Some(url) = caps.get("se:cdp").and_then(Value::as_str) {
+        return Some(WebSocketUrl::Cdp(url.to_string()));
+    }
+    let edge_address = caps
+        .get("ms:edgeOptions")
+        .and_then(|options| options["debuggerAddress"].as_str())
+        .map(String::from);
+    let address = ChromeCapabilities::from(caps).get_debugger_address();
+    let address = if address.is_empty() {
+        edge_address
+    } else {
+        Some(address)
+    };
+    address.map(WebSocketUrl::DebuggerAddress)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::fakedriver::FakeWebDriver;
+
+    fn error(status: u16, error: &str, message: &str) -> Option<(u16, Value)> {
+        Some((status, json!({"error": error, "message": message, "stacktrace": ""})))
+    }
+
+    #[tokio::test]
+    async fn network_conditions_restored() {
+        let server = FakeWebDriver::start_with(|req| match req.method.as_str() {
+            "GET" if req.path == "chromium/network_conditions" => error(
+                500,
+                "unknown error",
+                "network conditions must be set before it can be retrieved",
+            ),
+            _ => None,
+        })
+        .await;
+        let driver = server.connect().await;
+        let dev_tools = ChromeDevTools::new(driver.handle.clone());
+        dev_tools
+            .with_network_conditions(&NetworkConditions::offline(), async { Ok(()) })
+            .await
+            .unwrap();
+        let methods: Vec<String> = server
+            .requests()
+            .into_iter()
+            .filter(|r| r.path == "chromium/network_conditions")
+            .map(|r| r.method)
+            .collect();
+        assert_eq!(methods, vec!["GET", "POST", "DELETE"]);
+    }
+
+    #[tokio::test]
+    async fn network_conditions_errors_not_hidden() {
+        let server = FakeWebDriver::start_with(|req| match req.method.as_str() {
+            "GET" if req.path == "chromium/network_conditions" => {
+                error(404, "invalid session id", "session deleted")
+            }
+            _ => None,
+        })
+        .await;
+        let driver = server.connect().await;
+        let dev_tools = ChromeDevTools::new(driver.handle.clone());
+        let result = dev_tools
+            .with_network_conditions(&NetworkConditions::offline(), async { Ok(()) })
+            .await;
+        assert!(result.is_err());
+        let requests = server.requests();
+        let mut conditions = requests.iter().filter(|r| r.path == "chromium/network_conditions");
+        assert!(conditions.all(|r| r.method == "GET"));
+    }
+
+    #[test]
+    fn websocket_url_from_vendor_options() {
+        let caps = |v: Value| match v {
+            Value::Object(caps) => caps,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            websocket_url_from_caps(caps(json!({"se:cdp": "ws://grid/cdp"}))),
+            Some(WebSocketUrl::Cdp("ws://grid/cdp".to_string()))
+        );
+        assert_eq!(
+            websocket_url_from_caps(caps(
+                json!({"goog:chromeOptions": {"debuggerAddress": "a:1"}})
+            )),
+            Some(WebSocketUrl::DebuggerAddress("a:1".to_string()))
+        );
+        assert_eq!(
+            websocket_url_from_caps(caps(json!({"ms:edgeOptions": {"debuggerAddress": "b:2"}}))),
+            Some(WebSocketUrl::DebuggerAddress("b:2".to_string()))
+        );
+        assert_eq!(websocket_url_from_caps(caps(json!({"browserName": "firefox"}))), None);
+    }
+}
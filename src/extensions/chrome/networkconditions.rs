@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+/// The connection type to emulate. Serialized in lowercase, e.g. `cellular3g`.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
-#[serde(rename = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum ConnectionType {
     None,
     Cellular2G,
@@ -14,14 +15,23 @@ pub enum ConnectionType {
     Other,
 }
 
+/// Network conditions to emulate.
+///
+/// Throughput is in bytes per second, where -1 disables throttling, and latency is in
+/// milliseconds. The fields are serialized in snake_case, which is what chromedriver's
+/// `network_conditions` endpoint expects.
+///
+/// The presets match the throttling profiles in Chrome DevTools.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
-#[serde(rename = "camelCase")]
+#[serde(rename_all = "snake_case")]
 pub struct NetworkConditions {
     pub offline: bool,
     pub latency: u32,
+    #[serde(alias = "downloadThroughput")]
     pub download_throughput: i32,
+    #[serde(alias = "uploadThroughput")]
     pub upload_throughput: i32,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", alias = "connectionType")]
     pub connection_type: Option<ConnectionType>,
 }
 
@@ -41,4 +51,78 @@ impl NetworkConditions {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// No network connection.
+    pub fn offline() -> Self {
+        Self {
+            offline: true,
+            latency: 0,
+            download_throughput: 0,
+            upload_throughput: 0,
+            connection_type: Some(ConnectionType::None),
+        }
+    }
+
+    /// Slow 3G: 2000ms latency, 50 KB/s down and up.
+    pub fn slow_3g() -> Self {
+        Self {
+            offline: false,
+            latency: 2000,
+            download_throughput: 50_000,
+            upload_throughput: 50_000,
+            connection_type: Some(ConnectionType::Cellular3G),
+        }
+    }
+
+    /// Fast 3G: 563ms latency, 180 KB/s down and 84 KB/s up.
+    pub fn fast_3g() -> Self {
+        Self {
+            offline: false,
+            latency: 563,
+            download_throughput: 180_000,
+            upload_throughput: 84_375,
+            connection_type: Some(ConnectionType::Cellular3G),
+        }
+    }
+
+    /// 4G: 165ms latency, 1 MB/s down and 169 KB/s up.
+    pub fn fast_4g() -> Self {
+        Self {
+            offline: false,
+            latency: 165,
+            download_throughput: 1_012_500,
+            upload_throughput: 168_750,
+            connection_type: Some(ConnectionType::Cellular4G),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_for_chromedriver() {
+        assert_eq!(
+            serde_json::to_value(NetworkConditions::slow_3g()).unwrap(),
+            json!({
+                "offline": false,
+                "latency": 2000,
+                "download_throughput": 50000,
+                "upload_throughput": 50000,
+                "connection_type": "cellular3g"
+            })
+        );
+
+        let conditions: NetworkConditions = serde_json::from_value(json!({
+            "offline": true,
+            "latency": 0,
+            "downloadThroughput": 0,
+            "uploadThroughput": 0
+        }))
+        .unwrap();
+        assert!(conditions.offline);
+        assert_eq!(conditions.connection_type, None);
+    }
 }
@@ -20,6 +20,7 @@
 //! - Shadow DOM support
 //! - Alert support
 //! - Capture / Save screenshot of browser or individual element as PNG
+//! - Print pages to PDF
 //! - Chrome DevTools Protocol (CDP) support
 //! - Advanced query interface including explicit waits and various predicates
 //!
@@ -115,6 +116,8 @@ pub use common::{
         safari::SafariCapabilities,
     },
     command::By,
+    print::{PageRange, PrintMargins, PrintOptions, PrintOrientation, PrintPage},
+    shortcut::{Shortcut, ShortcutKey},
     types::*,
 };
 
@@ -138,12 +141,20 @@ pub mod prelude {
     pub use fantoccini::key::Key;
 }
 
+pub mod a11y;
 /// Action chains allow for more complex user interactions with the keyboard and mouse.
 pub mod action_chain;
 mod alert;
+pub mod bidi;
 /// Everything related to driving the underlying WebDriver session.
 pub mod session {
+    pub mod console;
+    pub mod emulation;
     pub mod handle;
+    pub mod har;
+    pub mod interception;
+    pub mod page_ready;
+    pub mod performance;
     pub mod scriptret;
 }
 
@@ -166,7 +177,13 @@ pub mod common {
     }
     pub mod command;
     pub mod config;
+    pub(crate) mod connector;
+    #[cfg(test)]
+    pub(crate) mod fakedriver;
+    pub mod print;
+    pub mod shortcut;
     pub mod types;
+    pub(crate) mod websocket;
 }
 
 /// Extensions for specific browsers.
@@ -177,9 +194,31 @@ pub mod extensions {
         mod devtools;
         mod networkconditions;
 
+        /// Typed commands for the Chrome DevTools Protocol (CDP), grouped by domain.
+        ///
+        /// Use [`ChromeDevTools::execute_cdp_command()`] to send them, or
+        /// [`ChromeDevTools::connect()`] to also receive CDP events.
+        pub mod cdp {
+            mod command;
+            mod connection;
+            pub mod emulation;
+            pub mod fetch;
+            pub mod log;
+            pub mod network;
+            pub mod page;
+            pub mod performance;
+            pub mod runtime;
+            pub mod security;
+            pub mod storage;
+
+            pub(crate) use command::parse_cdp_error;
+            pub use command::{error_code, CdpCommand, CdpEventType, Empty};
+            pub use connection::{CdpConnection, CdpEvent, CdpEventStream};
+        }
+
         pub use chromecommand::ChromeCommand;
         pub use devtools::ChromeDevTools;
-        pub use networkconditions::NetworkConditions;
+        pub use networkconditions::{ConnectionType, NetworkConditions};
     }
 
     /// Extensions for working with Firefox.
@@ -58,6 +58,20 @@ pub fn element_is_not_displayed(ignore_errors: bool) -> ElementPredicate {
     Box::new(move |elem| Box::pin(async move { negate(elem.is_displayed().await, ignore_errors) }))
 }
 
+/// Predicate that returns true for elements that are at least partially within the viewport.
+pub fn element_is_in_viewport(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move { handle_errors(elem.is_in_viewport().await, ignore_errors) })
+    })
+}
+
+/// Predicate that returns true for elements that are entirely outside the viewport.
+pub fn element_is_not_in_viewport(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move { negate(elem.is_in_viewport().await, ignore_errors) })
+    })
+}
+
 /// Predicate that returns true for elements that are clickable.
 pub fn element_is_clickable(ignore_errors: bool) -> ElementPredicate {
     Box::new(move |elem| {
@@ -503,3 +517,60 @@ where
         })
     })
 }
+
+/// Predicate that returns true for elements that have the specified computed ARIA role.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn element_has_role<N>(role: N, ignore_errors: bool) -> ElementPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    Box::new(move |elem| {
+        let role = role.clone();
+        Box::pin(async move {
+            handle_errors(elem.computed_role().await.map(|x| role.is_match(&x)), ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true for elements that do not have the specified computed ARIA role.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn element_lacks_role<N>(role: N, ignore_errors: bool) -> ElementPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    Box::new(move |elem| {
+        let role = role.clone();
+        Box::pin(async move {
+            negate(elem.computed_role().await.map(|x| role.is_match(&x)), ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true for elements that have the specified computed accessible label.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn element_has_label<N>(label: N, ignore_errors: bool) -> ElementPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    Box::new(move |elem| {
+        let label = label.clone();
+        Box::pin(async move {
+            handle_errors(elem.computed_label().await.map(|x| label.is_match(&x)), ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true for elements that do not have the specified computed
+/// accessible label.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn element_lacks_label<N>(label: N, ignore_errors: bool) -> ElementPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    Box::new(move |elem| {
+        let label = label.clone();
+        Box::pin(async move {
+            negate(elem.computed_label().await.map(|x| label.is_match(&x)), ignore_errors)
+        })
+    })
+}
@@ -81,6 +81,16 @@ pub enum ElementQuerySource {
     Element(WebElement),
 }
 
+impl ElementQuerySource {
+    /// Get the SessionHandle for this source.
+    fn handle(&self) -> &SessionHandle {
+        match self {
+            ElementQuerySource::Driver(handle) => handle,
+            ElementQuerySource::Element(element) => &element.handle,
+        }
+    }
+}
+
 /// High-level interface for performing powerful element queries using a
 /// builder pattern.
 ///
@@ -114,13 +124,14 @@ pub struct ElementQuery {
 }
 
 impl ElementQuery {
-    fn new(source: ElementQuerySource, poller: ElementPoller, by: By) -> Self {
+    fn new(source: ElementQuerySource, by: By) -> Self {
+        let settings = source.handle().config.get_settings();
         let selector = ElementSelector::new(by);
         Self {
             source,
-            poller,
+            poller: settings.query.poller(),
             selectors: vec![selector],
-            ignore_errors: true,
+            ignore_errors: settings.query.ignore_errors,
             description: String::new(),
         }
     }
@@ -213,8 +224,7 @@ impl ElementQuery {
         let mut elements = self.run_poller(false).await?;
 
         if elements.is_empty() {
-            let err = no_such_element(&self.selectors, &self.description);
-            Err(err)
+            Err(self.not_found().await)
         } else {
             Ok(elements.remove(0))
         }
@@ -231,8 +241,7 @@ impl ElementQuery {
         if elements.len() == 1 {
             Ok(elements.remove(0))
         } else {
-            let err = no_such_element(&self.selectors, &self.description);
-            Err(err)
+            Err(self.not_found().await)
         }
     }
 
@@ -250,7 +259,7 @@ impl ElementQuery {
         let elements = self.run_poller(false).await?;
 
         if elements.is_empty() {
-            Err(no_such_element(&self.selectors, &self.description))
+            Err(self.not_found().await)
         } else {
             Ok(elements)
         }
@@ -260,6 +269,18 @@ impl ElementQuery {
     // Helper Retrievers
     //
 
+    /// Save a failure screenshot (if configured) and return the NoSuchElement error.
+    async fn not_found(&self) -> WebDriverError {
+        let err = no_such_element(&self.selectors, &self.description);
+        let label = if self.description.is_empty() {
+            get_selector_summary(&self.selectors)
+        } else {
+            self.description.clone()
+        };
+        self.source.handle().save_failure_screenshot(&label).await;
+        err
+    }
+
     /// Run the poller for this ElementQuery and return the Vec of WebElements matched.
     /// NOTE: This function doesn't return a no_such_element error and the caller must handle it.
     async fn run_poller(&self, inverted: bool) -> WebDriverResult<Vec<WebElement>> {
@@ -361,6 +382,18 @@ impl ElementQuery {
         self.with_filter(conditions::element_is_not_displayed(ignore_errors))
     }
 
+    /// Only match elements that are at least partially within the viewport.
+    pub fn and_in_viewport(self) -> Self {
+        let ignore_errors = self.ignore_errors;
+        self.with_filter(conditions::element_is_in_viewport(ignore_errors))
+    }
+
+    /// Only match elements that are entirely outside the viewport.
+    pub fn and_not_in_viewport(self) -> Self {
+        let ignore_errors = self.ignore_errors;
+        self.with_filter(conditions::element_is_not_in_viewport(ignore_errors))
+    }
+
     /// Only match elements that are clickable.
     pub fn and_clickable(self) -> Self {
         let ignore_errors = self.ignore_errors;
@@ -505,6 +538,46 @@ impl ElementQuery {
         self.with_filter(conditions::element_lacks_value(value, ignore_errors))
     }
 
+    /// Only match elements that have the specified computed ARIA role.
+    /// See the `Needle` documentation for more details on text matching rules.
+    pub fn with_role<N>(self, role: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.with_filter(conditions::element_has_role(role, ignore_errors))
+    }
+
+    /// Only match elements that do not have the specified computed ARIA role.
+    /// See the `Needle` documentation for more details on text matching rules.
+    pub fn without_role<N>(self, role: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.with_filter(conditions::element_lacks_role(role, ignore_errors))
+    }
+
+    /// Only match elements that have the specified computed accessible label.
+    /// See the `Needle` documentation for more details on text matching rules.
+    pub fn with_label<N>(self, label: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.with_filter(conditions::element_has_label(label, ignore_errors))
+    }
+
+    /// Only match elements that do not have the specified computed accessible label.
+    /// See the `Needle` documentation for more details on text matching rules.
+    pub fn without_label<N>(self, label: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.with_filter(conditions::element_lacks_label(label, ignore_errors))
+    }
+
     /// Only match elements that have the specified attribute with the specified value.
     /// See the `Needle` documentation for more details on text matching rules.
     pub fn with_attribute<S, N>(self, attribute_name: S, value: N) -> Self
@@ -667,8 +740,7 @@ impl ElementQueryable for WebElement {
     ///
     /// See [ElementQuery](query/struct.ElementQuery.html) for more documentation.
     fn query(&self, by: By) -> ElementQuery {
-        let poller: ElementPoller = self.handle.config.get_query_poller();
-        ElementQuery::new(ElementQuerySource::Element(self.clone()), poller, by)
+        ElementQuery::new(ElementQuerySource::Element(self.clone()), by)
     }
 }
 
@@ -680,8 +752,7 @@ impl ElementQueryable for WebDriver {
     ///
     /// See [ElementQuery](query/struct.ElementQuery.html) for more documentation.
     fn query(&self, by: By) -> ElementQuery {
-        let poller: ElementPoller = self.handle.config.get_query_poller();
-        ElementQuery::new(ElementQuerySource::Driver(self.handle.clone()), poller, by)
+        ElementQuery::new(ElementQuerySource::Driver(self.handle.clone()), by)
     }
 }
 
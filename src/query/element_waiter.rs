@@ -37,12 +37,13 @@ pub struct ElementWaiter {
 }
 
 impl ElementWaiter {
-    fn new(element: WebElement, poller: ElementPoller) -> Self {
+    fn new(element: WebElement) -> Self {
+        let settings = element.handle.config.get_settings();
         Self {
             element,
-            poller,
+            poller: settings.query.poller(),
             message: String::new(),
-            ignore_errors: true,
+            ignore_errors: settings.query.ignore_errors,
         }
     }
 
@@ -95,21 +96,27 @@ impl ElementWaiter {
         }
     }
 
-    fn timeout(self) -> WebDriverResult<()> {
+    async fn timeout(self) -> WebDriverResult<()> {
+        let label = if self.message.is_empty() {
+            "wait_until"
+        } else {
+            &self.message
+        };
+        self.element.handle.save_failure_screenshot(label).await;
         Err(WebDriverError::Timeout(self.message))
     }
 
     pub async fn condition(self, f: ElementPredicate) -> WebDriverResult<()> {
         match self.run_poller(vec![f]).await? {
             true => Ok(()),
-            false => self.timeout(),
+            false => self.timeout().await,
         }
     }
 
     pub async fn conditions(self, conditions: Vec<ElementPredicate>) -> WebDriverResult<()> {
         match self.run_poller(conditions).await? {
             true => Ok(()),
-            false => self.timeout(),
+            false => self.timeout().await,
         }
     }
 
@@ -133,6 +140,16 @@ impl ElementWaiter {
         self.condition(conditions::element_is_not_displayed(ignore_errors)).await
     }
 
+    pub async fn in_viewport(self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_in_viewport(ignore_errors)).await
+    }
+
+    pub async fn not_in_viewport(self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_not_in_viewport(ignore_errors)).await
+    }
+
     pub async fn selected(self) -> WebDriverResult<()> {
         let ignore_errors = self.ignore_errors;
         self.condition(conditions::element_is_selected(ignore_errors)).await
@@ -158,6 +175,39 @@ impl ElementWaiter {
         self.condition(conditions::element_is_clickable(ignore_errors)).await
     }
 
+    /// Wait until the element is clickable, and then click it.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// elem.wait_until().clickable_then_click().await?;
+    /// ```
+    pub async fn clickable_then_click(self) -> WebDriverResult<()> {
+        let element = self.element.clone();
+        self.clickable().await?;
+        element.click().await
+    }
+
+    /// Wait until the element is clickable, and then move the mouse over it.
+    pub async fn clickable_then_hover(self) -> WebDriverResult<()> {
+        let element = self.element.clone();
+        self.clickable().await?;
+        element.hover().await
+    }
+
+    /// Wait until the element is clickable, and then double-click it.
+    pub async fn clickable_then_double_click(self) -> WebDriverResult<()> {
+        let element = self.element.clone();
+        self.clickable().await?;
+        element.double_click().await
+    }
+
+    /// Wait until the element is clickable, and then right-click it.
+    pub async fn clickable_then_right_click(self) -> WebDriverResult<()> {
+        let element = self.element.clone();
+        self.clickable().await?;
+        element.right_click().await
+    }
+
     pub async fn not_clickable(self) -> WebDriverResult<()> {
         let ignore_errors = self.ignore_errors;
         self.condition(conditions::element_is_not_clickable(ignore_errors)).await
@@ -211,6 +261,38 @@ impl ElementWaiter {
         self.condition(conditions::element_lacks_value(value, ignore_errors)).await
     }
 
+    pub async fn has_role<N>(self, role: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_has_role(role, ignore_errors)).await
+    }
+
+    pub async fn lacks_role<N>(self, role: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_lacks_role(role, ignore_errors)).await
+    }
+
+    pub async fn has_label<N>(self, label: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_has_label(label, ignore_errors)).await
+    }
+
+    pub async fn lacks_label<N>(self, label: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_lacks_label(label, ignore_errors)).await
+    }
+
     pub async fn has_attribute<S, N>(self, attribute_name: S, value: N) -> WebDriverResult<()>
     where
         S: Into<String>,
@@ -366,8 +448,7 @@ impl ElementWaitable for WebElement {
     ///
     /// See [ElementWaiter](query/struct.ElementWaiter.html) for more documentation.
     fn wait_until(&self) -> ElementWaiter {
-        let poller: ElementPoller = self.handle.config.get_query_poller();
-        ElementWaiter::new(self.clone(), poller)
+        ElementWaiter::new(self.clone())
     }
 }
 
@@ -141,6 +141,10 @@
 //! `NoWait` and `NumTriesWithInterval`.
 //! These can also be overridden on a per-query basis if needed.
 //!
+//! The default poller and the default `ignore_errors()` value can also be loaded from a
+//! TOML/JSON file or from environment variables. See
+//! [WebDriverSettings](../common/config/struct.WebDriverSettings.html) for more details.
+//!
 
 pub mod conditions;
 mod element_query;
@@ -0,0 +1,455 @@
+use futures::StreamExt;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+use crate::common::command::W3CCommand;
+use crate::error::{WebDriverError, WebDriverResult};
+use crate::extensions::chrome::cdp::{self, CdpConnection, CdpEvent};
+use crate::extensions::chrome::ChromeDevTools;
+use crate::session::handle::SessionHandle;
+
+/// Installs the console shim (once per document) and drains the entries recorded so far.
+const CONSOLE_SHIM_SCRIPT: &str = r#"
+    if (!window.__thirtyfour_console) {
+        const entries = [];
+        window.__thirtyfour_console = entries;
+        const format = a => {
+            if (typeof a === "string") return a;
+            if (a instanceof Error) return a.stack || String(a);
+            try { return JSON.stringify(a); } catch (e) { return String(a); }
+        };
+        const add = (level, kind, text, url, line) => entries.push({
+            level, kind, text, url: url || location.href, line: line || null, timestamp: Date.now()
+        });
+        const levels = { debug: "debug", log: "info", info: "info", warn: "warning", error: "error" };
+        for (const [method, level] of Object.entries(levels)) {
+            const orig = console[method];
+            console[method] = function(...args) {
+                add(level, "console", args.map(format).join(" "));
+                return orig.apply(this, args);
+            };
+        }
+        window.addEventListener("error", e => add("error", "exception", e.message, e.filename, e.lineno));
+        window.addEventListener("unhandledrejection", e =>
+            add("error", "exception", "Uncaught (in promise) " + format(e.reason)));
+    }
+    return window.__thirtyfour_console.splice(0);
+"#;
+
+/// The severity of a console entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConsoleLevel {
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+impl ConsoleLevel {
+    fn from_cdp(level: &str) -> Self {
+        match level {
+            "debug" | "verbose" | "trace" => ConsoleLevel::Debug,
+            "warning" => ConsoleLevel::Warning,
+            "error" | "assert" => ConsoleLevel::Error,
+            _ => ConsoleLevel::Info,
+        }
+    }
+
+    fn from_legacy(level: &str) -> Self {
+        match level {
+            "SEVERE" => ConsoleLevel::Error,
+            "WARNING" => ConsoleLevel::Warning,
+            "INFO" => ConsoleLevel::Info,
+            _ => ConsoleLevel::Debug,
+        }
+    }
+}
+
+/// What produced a console entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConsoleEntryKind {
+    /// A call to `console.log()`, `console.error()` etc.
+    Console,
+    /// An uncaught exception or unhandled promise rejection.
+    Exception,
+    /// A message from the browser itself, such as a failed network request.
+    Browser,
+}
+
+/// A single browser console entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsoleEntry {
+    pub level: ConsoleLevel,
+    pub kind: ConsoleEntryKind,
+    pub text: String,
+    /// The URL of the script or page that produced the entry, if known.
+    pub url: Option<String>,
+    /// The line number within `url`, if known.
+    pub line: Option<u64>,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+impl ConsoleEntry {
+    fn from_cdp_event(event: &CdpEvent) -> Option<Self> {
+        if let Some(Ok(call)) = event.parse::<cdp::runtime::ConsoleApiCalled>() {
+            let text = call.args.iter().map(remote_object_text).collect::<Vec<_>>().join(" ");
+            let frame = call.stack_trace.and_then(|s| s.call_frames.into_iter().next());
+            return Some(Self {
+                level: ConsoleLevel::from_cdp(&call.call_type),
+                kind: ConsoleEntryKind::Console,
+                text,
+                url: frame.as_ref().map(|f| f.url.clone()).filter(|u| !u.is_empty()),
+                line: frame.map(|f| f.line_number as u64 + 1),
+                timestamp: call.timestamp as u64,
+            });
+        }
+        if let Some(Ok(thrown)) = event.parse::<cdp::runtime::ExceptionThrown>() {
+            let details = thrown.exception_details;
+            let text = match details.exception.as_ref().and_then(|e| e.description.clone()) {
+                Some(description) => format!("{} {}", details.text, description),
+                None => details.text,
+            };
+            return Some(Self {
+                level: ConsoleLevel::Error,
+                kind: ConsoleEntryKind::Exception,
+                text,
+                url: details.url,
+                line: Some(details.line_number as u64 + 1),
+                timestamp: thrown.timestamp as u64,
+            });
+        }
+        if let Some(Ok(added)) = event.parse::<cdp::log::EntryAdded>() {
+            let entry = added.entry;
+            return Some(Self {
+                level: ConsoleLevel::from_cdp(&entry.level),
+                kind: ConsoleEntryKind::Browser,
+                text: entry.text,
+                url: entry.url,
+                line: entry.line_number.map(|l| l as u64 + 1),
+                timestamp: entry.timestamp as u64,
+            });
+        }
+        None
+    }
+
+    /// Parse an entry from the legacy log endpoint. Messages look like
+    /// `http://host/app.js 12:5 "some text"`.
+    fn from_legacy(entry: &LegacyLogEntry) -> Self {
+        let mut url = None;
+        let mut line = None;
+        let mut text = entry.message.as_str();
+        if let Some((first, rest)) = text.split_once(' ') {
+            if first.contains("://") {
+                url = Some(first.to_string());
+                text = rest;
+                if let Some((position, rest)) = text.split_once(' ') {
+                    if let Some(l) = position.split(':').next().and_then(|l| l.parse().ok()) {
+                        line = Some(l);
+                        text = rest;
+                    }
+                }
+            }
+        }
+
+        let kind = match entry.source.as_deref() {
+            Some("console-api") => ConsoleEntryKind::Console,
+            Some("javascript") => ConsoleEntryKind::Exception,
+            _ if text.starts_with("Uncaught") => ConsoleEntryKind::Exception,
+            _ if text.starts_with('"') => ConsoleEntryKind::Console,
+            _ => ConsoleEntryKind::Browser,
+        };
+        let text = match kind {
+            ConsoleEntryKind::Console => {
+                serde_json::from_str::<String>(text).unwrap_or_else(|_| text.to_string())
+            }
+            _ => text.to_string(),
+        };
+        Self {
+            level: ConsoleLevel::from_legacy(&entry.level),
+            kind,
+            text,
+            url,
+            line,
+            timestamp: entry.timestamp,
+        }
+    }
+}
+
+/// Format a console argument the way the DevTools console would.
+fn remote_object_text(object: &cdp::runtime::RemoteObject) -> String {
+    match &object.value {
+        Some(Value::String(s)) => s.clone(),
+        Some(v) if object.description.is_none() => v.to_string(),
+        _ => object.description.clone().unwrap_or_else(|| object.object_type.clone()),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LegacyLogEntry {
+    level: String,
+    message: String,
+    timestamp: u64,
+    #[serde(default)]
+    source: Option<String>,
+}
+
+/// The capabilities that enable the legacy log endpoint.
+const LOGGING_PREFS: [&str; 2] = ["goog:loggingPrefs", "ms:loggingPrefs"];
+
+/// Where a [`ConsoleLog`] gets its entries from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleLogSource {
+    /// CDP `Runtime` and `Log` events. Chromium-based browsers only, and requires a
+    /// reachable DevTools websocket (see [`ChromeDevTools::connect()`]).
+    Cdp,
+    /// The legacy log endpoint. Requires the `goog:loggingPrefs` (or `ms:loggingPrefs`)
+    /// capability to be set to `{"browser": "ALL"}`.
+    Legacy,
+    /// A script that wraps the `console` methods and listens for uncaught errors.
+    /// Only entries logged after the current page loaded (and after the first call
+    /// to `entries()` on later pages) are captured.
+    Shim,
+}
+
+/// Collects browser console messages and uncaught JavaScript exceptions.
+///
+/// Entries are collected from the moment the `ConsoleLog` is created, using CDP on
+/// Chromium-based browsers where possible, then the legacy log endpoint if the
+/// logging prefs capability is set, and finally an injected shim. Collection stops when the `ConsoleLog` is dropped.
+///
+/// # Example:
+/// ```ignore
+/// let console = driver.console_log().await?;
+/// driver.get("http://webappdemo").await?;
+/// // ...
+/// console.assert_no_errors().await?;
+/// ```
+pub struct ConsoleLog {
+    handle: SessionHandle,
+    source: ConsoleLogSource,
+    entries: Arc<Mutex<Vec<ConsoleEntry>>>,
+    // Keeps the CDP connection open while the collector task is running.
+    cdp_task: Option<(CdpConnection, JoinHandle<()>)>,
+}
+
+impl Drop for ConsoleLog {
+    fn drop(&mut self) {
+        if let Some((_, task)) = &self.cdp_task {
+            task.abort();
+        }
+    }
+}
+
+impl ConsoleLog {
+    /// Start collecting console entries using the best source available.
+    pub async fn new(handle: SessionHandle) -> WebDriverResult<Self> {
+        if handle.is_chromium() {
+            match Self::with_source(handle.clone(), ConsoleLogSource::Cdp).await {
+                Ok(log) => return Ok(log),
+                Err(e) => log::debug!("console log: CDP is not available: {}", e),
+            }
+        }
+        // Without logging prefs, chromedriver's log endpoint succeeds but is always empty.
+        let caps = handle.config.get_capabilities();
+        if LOGGING_PREFS.iter().any(|key| caps.get(*key).is_some()) {
+            match Self::with_source(handle.clone(), ConsoleLogSource::Legacy).await {
+                Ok(log) => return Ok(log),
+                Err(e) => {
+                    log::debug!("console log: the legacy log endpoint is not available: {}", e)
+                }
+            }
+        }
+        Self::with_source(handle, ConsoleLogSource::Shim).await
+    }
+
+    /// Start collecting console entries using the specified source.
+    pub async fn with_source(
+        handle: SessionHandle,
+        source: ConsoleLogSource,
+    ) -> WebDriverResult<Self> {
+        let mut log = Self {
+            handle,
+            source,
+            entries: Arc::new(Mutex::new(Vec::new())),
+            cdp_task: None,
+        };
+        match source {
+            ConsoleLogSource::Cdp => {
+                let cdp = ChromeDevTools::new(log.handle.clone()).connect().await?;
+                let mut events = cdp.events();
+                let entries = log.entries.clone();
+                let task = tokio::spawn(async move {
+                    while let Some(event) = events.next().await {
+                        if let Some(entry) = ConsoleEntry::from_cdp_event(&event) {
+                            entries.lock().push(entry);
+                        }
+                    }
+                });
+                log.cdp_task = Some((cdp.clone(), task));
+                cdp.execute_cdp_command(&cdp::runtime::Enable {}).await?;
+                cdp.execute_cdp_command(&cdp::log::Enable {}).await?;
+            }
+            // Fail now if the endpoint is not supported, and discard the entries logged
+            // before this ConsoleLog was created.
+            ConsoleLogSource::Legacy => {
+                log.handle.client.issue_cmd(W3CCommand::GetLog("browser".to_string())).await?;
+            }
+            // Fail now if the shim cannot be installed.
+            ConsoleLogSource::Shim => log.collect().await?,
+        }
+        Ok(log)
+    }
+
+    /// The source this log is collecting from.
+    pub fn source(&self) -> ConsoleLogSource {
+        self.source
+    }
+
+    /// Fetch any new entries from the browser, for sources that need polling.
+    async fn collect(&self) -> WebDriverResult<()> {
+        let new_entries: Vec<ConsoleEntry> = match self.source {
+            ConsoleLogSource::Cdp => return Ok(()),
+            ConsoleLogSource::Legacy => {
+                let v =
+                    self.handle.client.issue_cmd(W3CCommand::GetLog("browser".to_string())).await?;
+                let entries: Vec<LegacyLogEntry> = serde_json::from_value(v)?;
+                entries.iter().map(ConsoleEntry::from_legacy).collect()
+            }
+            ConsoleLogSource::Shim => {
+                let ret = self.handle.execute_script(CONSOLE_SHIM_SCRIPT, Vec::new()).await?;
+                ret.convert()?
+            }
+        };
+        self.entries.lock().extend(new_entries);
+        Ok(())
+    }
+
+    /// Get all entries collected so far.
+    pub async fn entries(&self) -> WebDriverResult<Vec<ConsoleEntry>> {
+        self.collect().await?;
+        Ok(self.entries.lock().clone())
+    }
+
+    /// Get all entries collected so far with the specified level or higher.
+    pub async fn entries_at_least(
+        &self,
+        level: ConsoleLevel,
+    ) -> WebDriverResult<Vec<ConsoleEntry>> {
+        let entries = self.entries().await?;
+        Ok(entries.into_iter().filter(|e| e.level >= level).collect())
+    }
+
+    /// Get all errors collected so far, including uncaught exceptions.
+    pub async fn errors(&self) -> WebDriverResult<Vec<ConsoleEntry>> {
+        self.entries_at_least(ConsoleLevel::Error).await
+    }
+
+    /// Discard all entries collected so far.
+    pub async fn clear(&self) -> WebDriverResult<()> {
+        self.collect().await?;
+        self.entries.lock().clear();
+        Ok(())
+    }
+
+    /// Return an error listing the console errors, if any were collected.
+    pub async fn assert_no_errors(&self) -> WebDriverResult<()> {
+        let errors = self.errors().await?;
+        if errors.is_empty() {
+            return Ok(());
+        }
+        let messages: Vec<String> = errors
+            .iter()
+            .map(|e| match &e.url {
+                Some(url) => format!("{} ({})", e.text, url),
+                None => e.text.clone(),
+            })
+            .collect();
+        Err(WebDriverError::CustomError(format!(
+            "{} console error(s):\n{}",
+            errors.len(),
+            messages.join("\n")
+        )))
+    }
+}
+
+impl SessionHandle {
+    /// Start collecting browser console messages and uncaught exceptions.
+    ///
+    /// See [`ConsoleLog`] for details.
+    pub async fn console_log(&self) -> WebDriverResult<ConsoleLog> {
+        ConsoleLog::new(self.clone()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::fakedriver::FakeWebDriver;
+    use crate::{CapabilitiesHelper, DesiredCapabilities};
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn legacy_requires_logging_prefs() {
+        let server = FakeWebDriver::start_with(|req| match req.path.as_str() {
+            "execute/sync" => Some((200, json!([]))),
+            _ => None,
+        })
+        .await;
+        let driver = server.connect().await;
+        let console = driver.console_log().await.unwrap();
+        assert_eq!(console.source(), ConsoleLogSource::Shim);
+        assert!(server.requests().iter().all(|r| r.path != "se/log"));
+    }
+
+    #[tokio::test]
+    async fn legacy_discards_backlog() {
+        let calls = AtomicUsize::new(0);
+        let server = FakeWebDriver::start_with(move |req| match req.path.as_str() {
+            "se/log" if calls.fetch_add(1, Ordering::SeqCst) == 0 => Some((
+                200,
+                json!([{"level": "SEVERE", "message": "old 1:1 \"before\"", "timestamp": 1}]),
+            )),
+            "se/log" => Some((200, json!([]))),
+            _ => None,
+        })
+        .await;
+        let mut caps = DesiredCapabilities::chrome();
+        caps.add("goog:loggingPrefs", json!({"browser": "ALL"})).unwrap();
+        let driver = server.connect_with(caps).await;
+
+        let console = driver.console_log().await.unwrap();
+        assert_eq!(console.source(), ConsoleLogSource::Legacy);
+        assert!(console.entries().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_legacy_entries() {
+        let entry = ConsoleEntry::from_legacy(&LegacyLogEntry {
+            level: "SEVERE".to_string(),
+            message: r#"http://localhost/app.js 12:5 "boom \"quoted\"""#.to_string(),
+            timestamp: 1000,
+            source: Some("console-api".to_string()),
+        });
+        assert_eq!(entry.level, ConsoleLevel::Error);
+        assert_eq!(entry.kind, ConsoleEntryKind::Console);
+        assert_eq!(entry.text, r#"boom "quoted""#);
+        assert_eq!(entry.url.as_deref(), Some("http://localhost/app.js"));
+        assert_eq!(entry.line, Some(12));
+
+        let entry = ConsoleEntry::from_legacy(&LegacyLogEntry {
+            level: "SEVERE".to_string(),
+            message: "http://localhost/app.js 3:1 Uncaught Error: oops".to_string(),
+            timestamp: 1000,
+            source: None,
+        });
+        assert_eq!(entry.kind, ConsoleEntryKind::Exception);
+        assert_eq!(entry.text, "Uncaught Error: oops");
+    }
+}
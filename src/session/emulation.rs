@@ -0,0 +1,571 @@
+use serde_json::{json, Value};
+
+use crate::common::capabilities::chrome::{
+    ChromeCapabilities, MobileDeviceMetrics, MobileEmulation,
+};
+use crate::error::{WebDriverError, WebDriverResult};
+use crate::extensions::chrome::cdp::emulation::{
+    ClearDeviceMetricsOverride, ClearGeolocationOverride, MediaFeature, SetDeviceMetricsOverride,
+    SetEmulatedMedia, SetGeolocationOverride, SetLocaleOverride, SetTimezoneOverride,
+    SetTouchEmulationEnabled, SetUserAgentOverride,
+};
+use crate::extensions::chrome::ChromeDevTools;
+use crate::session::handle::SessionHandle;
+
+const IPHONE_USER_AGENT: &str = "Mozilla/5.0 (iPhone; CPU iPhone OS 16_0 like Mac OS X) \
+    AppleWebKit/605.1.15 (KHTML, like Gecko) Version/16.0 Mobile/15E148 Safari/604.1";
+const IPAD_USER_AGENT: &str = "Mozilla/5.0 (iPad; CPU OS 16_0 like Mac OS X) \
+    AppleWebKit/605.1.15 (KHTML, like Gecko) Version/16.0 Mobile/15E148 Safari/604.1";
+
+/// A device that can be emulated with [`Emulation::device()`].
+///
+/// The dimensions and user agents match the device list in Chrome DevTools.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Device {
+    pub name: &'static str,
+    pub width: u32,
+    pub height: u32,
+    pub device_scale_factor: f64,
+    pub mobile: bool,
+    pub touch: bool,
+    pub user_agent: &'static str,
+}
+
+impl Device {
+    /// Find the device with the specified name in [`DEVICES`], ignoring case.
+    pub fn find(name: &str) -> Option<&'static Device> {
+        DEVICES.iter().find(|device| device.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// The named devices available to [`Emulation::device()`].
+pub const DEVICES: &[Device] = &[
+    Device {
+        name: "iPhone SE",
+        width: 375,
+        height: 667,
+        device_scale_factor: 2.0,
+        mobile: true,
+        touch: true,
+        user_agent: IPHONE_USER_AGENT,
+    },
+    Device {
+        name: "iPhone 12 Pro",
+        width: 390,
+        height: 844,
+        device_scale_factor: 3.0,
+        mobile: true,
+        touch: true,
+        user_agent: IPHONE_USER_AGENT,
+    },
+    Device {
+        name: "iPhone 14 Pro Max",
+        width: 430,
+        height: 932,
+        device_scale_factor: 3.0,
+        mobile: true,
+        touch: true,
+        user_agent: IPHONE_USER_AGENT,
+    },
+    Device {
+        name: "Pixel 5",
+        width: 393,
+        height: 851,
+        device_scale_factor: 2.75,
+        mobile: true,
+        touch: true,
+        user_agent: "Mozilla/5.0 (Linux; Android 11; Pixel 5) AppleWebKit/537.36 \
+            (KHTML, like Gecko) Chrome/116.0.0.0 Mobile Safari/537.36",
+    },
+    Device {
+        name: "Pixel 7",
+        width: 412,
+        height: 915,
+        device_scale_factor: 2.625,
+        mobile: true,
+        touch: true,
+        user_agent: "Mozilla/5.0 (Linux; Android 13; Pixel 7) AppleWebKit/537.36 \
+            (KHTML, like Gecko) Chrome/116.0.0.0 Mobile Safari/537.36",
+    },
+    Device {
+        name: "Samsung Galaxy S20 Ultra",
+        width: 412,
+        height: 915,
+        device_scale_factor: 3.5,
+        mobile: true,
+        touch: true,
+        user_agent: "Mozilla/5.0 (Linux; Android 13; SM-G981B) AppleWebKit/537.36 \
+            (KHTML, like Gecko) Chrome/116.0.0.0 Mobile Safari/537.36",
+    },
+    Device {
+        name: "iPad Mini",
+        width: 768,
+        height: 1024,
+        device_scale_factor: 2.0,
+        mobile: true,
+        touch: true,
+        user_agent: IPAD_USER_AGENT,
+    },
+    Device {
+        name: "iPad Air",
+        width: 820,
+        height: 1180,
+        device_scale_factor: 2.0,
+        mobile: true,
+        touch: true,
+        user_agent: IPAD_USER_AGENT,
+    },
+    Device {
+        name: "Surface Pro 7",
+        width: 912,
+        height: 1368,
+        device_scale_factor: 2.0,
+        mobile: true,
+        touch: true,
+        user_agent: "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
+            (KHTML, like Gecko) Chrome/116.0.0.0 Safari/537.36",
+    },
+];
+
+/// The viewport to emulate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeviceMetrics {
+    pub width: u32,
+    pub height: u32,
+    pub device_scale_factor: f64,
+    /// Emulate a mobile device, including the viewport meta tag and overlay scrollbars.
+    pub mobile: bool,
+}
+
+/// The geolocation position to emulate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Geolocation {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Accuracy in metres.
+    pub accuracy: f64,
+}
+
+/// The value of the `prefers-color-scheme` media feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorScheme {
+    Light,
+    Dark,
+}
+
+impl ColorScheme {
+    fn as_str(self) -> &'static str {
+        match self {
+            ColorScheme::Light => "light",
+            ColorScheme::Dark => "dark",
+        }
+    }
+}
+
+/// The value of the `prefers-reduced-motion` media feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReducedMotion {
+    Reduce,
+    NoPreference,
+}
+
+impl ReducedMotion {
+    fn as_str(self) -> &'static str {
+        match self {
+            ReducedMotion::Reduce => "reduce",
+            ReducedMotion::NoPreference => "no-preference",
+        }
+    }
+}
+
+/// Device and environment settings to emulate.
+///
+/// On Chromium-based browsers the settings can be changed for a running session with
+/// [`SessionHandle::emulate()`], which uses the CDP `Emulation` domain. Otherwise use
+/// [`Emulation::apply_to_capabilities()`] to apply them when the session is created.
+///
+/// # Example:
+/// ```ignore
+/// use thirtyfour::session::emulation::{ColorScheme, Emulation};
+///
+/// let emulation = Emulation::device("iPhone 12 Pro")?
+///     .with_color_scheme(ColorScheme::Dark)
+///     .with_timezone("Europe/Berlin")
+///     .with_locale("de-DE");
+/// driver.emulate(&emulation).await?;
+/// driver.get("http://webappdemo").await?;
+/// // ...
+/// driver.clear_emulation().await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Emulation {
+    viewport: Option<(u32, u32)>,
+    device_scale_factor: Option<f64>,
+    mobile: Option<bool>,
+    touch: Option<bool>,
+    user_agent: Option<String>,
+    geolocation: Option<Geolocation>,
+    timezone: Option<String>,
+    locale: Option<String>,
+    color_scheme: Option<ColorScheme>,
+    reduced_motion: Option<ReducedMotion>,
+}
+
+impl Emulation {
+    /// Emulate nothing. Use the `with_*` methods to choose what to emulate.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emulate the viewport, touch support and user agent of the device with the
+    /// specified name. See [`DEVICES`] for the available devices.
+    pub fn device(name: &str) -> WebDriverResult<Self> {
+        Device::find(name).map(Self::from_device).ok_or_else(|| {
+            WebDriverError::CustomError(format!("unknown device for emulation: {}", name))
+        })
+    }
+
+    /// Emulate the viewport, touch support and user agent of the specified device.
+    pub fn from_device(device: &Device) -> Self {
+        Self::new()
+            .with_device_metrics(DeviceMetrics {
+                width: device.width,
+                height: device.height,
+                device_scale_factor: device.device_scale_factor,
+                mobile: device.mobile,
+            })
+            .with_touch(device.touch)
+            .with_user_agent(device.user_agent)
+    }
+
+    /// Set the viewport size, device scale factor and mobile flag.
+    pub fn with_device_metrics(mut self, metrics: DeviceMetrics) -> Self {
+        self.viewport = Some((metrics.width, metrics.height));
+        self.device_scale_factor = Some(metrics.device_scale_factor);
+        self.mobile = Some(metrics.mobile);
+        self
+    }
+
+    /// Set the viewport size in CSS pixels.
+    pub fn with_viewport(mut self, width: u32, height: u32) -> Self {
+        self.viewport = Some((width, height));
+        self
+    }
+
+    /// Set the device scale factor. Without a viewport, the window size is kept.
+    pub fn with_device_scale_factor(mut self, device_scale_factor: f64) -> Self {
+        self.device_scale_factor = Some(device_scale_factor);
+        self
+    }
+
+    /// Set the mobile flag. Without a viewport, the window size is kept.
+    pub fn with_mobile(mut self, mobile: bool) -> Self {
+        self.mobile = Some(mobile);
+        self
+    }
+
+    /// Set whether touch events are supported.
+    pub fn with_touch(mut self, touch: bool) -> Self {
+        self.touch = Some(touch);
+        self
+    }
+
+    /// Set the user agent.
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Set the geolocation position, with an accuracy of 100 metres.
+    pub fn with_geolocation(mut self, latitude: f64, longitude: f64) -> Self {
+        self.geolocation = Some(Geolocation {
+            latitude,
+            longitude,
+            accuracy: 100.0,
+        });
+        self
+    }
+
+    /// Set the timezone, e.g. `Europe/Berlin`.
+    pub fn with_timezone(mut self, timezone: &str) -> Self {
+        self.timezone = Some(timezone.to_string());
+        self
+    }
+
+    /// Set the locale, e.g. `de-DE`. This affects `Intl` formatting,
+    /// `navigator.language` and the `Accept-Language` header.
+    pub fn with_locale(mut self, locale: &str) -> Self {
+        self.locale = Some(locale.to_string());
+        self
+    }
+
+    /// Set the `prefers-color-scheme` media feature.
+    pub fn with_color_scheme(mut self, color_scheme: ColorScheme) -> Self {
+        self.color_scheme = Some(color_scheme);
+        self
+    }
+
+    /// Set the `prefers-reduced-motion` media feature.
+    pub fn with_reduced_motion(mut self, reduced_motion: ReducedMotion) -> Self {
+        self.reduced_motion = Some(reduced_motion);
+        self
+    }
+
+    /// The device metrics to emulate, if any of them are set. A width and height of 0
+    /// keep the window size, and the device scale factor and mobile flag default to
+    /// 1.0 and false.
+    fn device_metrics(&self) -> Option<DeviceMetrics> {
+        if self.viewport.is_none() && self.device_scale_factor.is_none() && self.mobile.is_none() {
+            return None;
+        }
+        let (width, height) = self.viewport.unwrap_or_default();
+        Some(DeviceMetrics {
+            width,
+            height,
+            device_scale_factor: self.device_scale_factor.unwrap_or(1.0),
+            mobile: self.mobile.unwrap_or_default(),
+        })
+    }
+
+    /// Apply these settings to the running session using the CDP `Emulation` domain.
+    ///
+    /// Settings that are not set are left unchanged.
+    pub async fn apply(&self, dev_tools: &ChromeDevTools) -> WebDriverResult<()> {
+        if let Some(metrics) = self.device_metrics() {
+            dev_tools
+                .execute_cdp_command(&SetDeviceMetricsOverride {
+                    width: metrics.width,
+                    height: metrics.height,
+                    device_scale_factor: metrics.device_scale_factor,
+                    mobile: metrics.mobile,
+                })
+                .await?;
+        }
+        if let Some(touch) = self.touch {
+            dev_tools
+                .execute_cdp_command(&SetTouchEmulationEnabled {
+                    enabled: touch,
+                    max_touch_points: touch.then_some(5),
+                })
+                .await?;
+        }
+        if self.user_agent.is_some() || self.locale.is_some() {
+            let user_agent = match &self.user_agent {
+                Some(user_agent) => user_agent.clone(),
+                None => {
+                    let version = dev_tools.execute_cdp("Browser.getVersion").await?;
+                    version["userAgent"].as_str().unwrap_or_default().to_string()
+                }
+            };
+            dev_tools
+                .execute_cdp_command(&SetUserAgentOverride {
+                    user_agent,
+                    accept_language: self.locale.clone(),
+                    platform: None,
+                })
+                .await?;
+        }
+        if let Some(geolocation) = &self.geolocation {
+            // Without the permission, the page only sees the position after a prompt.
+            if let Err(e) = dev_tools
+                .execute_cdp_with_params(
+                    "Browser.grantPermissions",
+                    json!({"permissions": ["geolocation"]}),
+                )
+                .await
+            {
+                log::debug!("emulation: unable to grant the geolocation permission: {}", e);
+            }
+            dev_tools
+                .execute_cdp_command(&SetGeolocationOverride {
+                    latitude: Some(geolocation.latitude),
+                    longitude: Some(geolocation.longitude),
+                    accuracy: Some(geolocation.accuracy),
+                })
+                .await?;
+        }
+        if let Some(timezone) = &self.timezone {
+            dev_tools
+                .execute_cdp_command(&SetTimezoneOverride {
+                    timezone_id: timezone.clone(),
+                })
+                .await?;
+        }
+        if let Some(locale) = &self.locale {
+            dev_tools
+                .execute_cdp_command(&SetLocaleOverride {
+                    locale: Some(locale.clone()),
+                })
+                .await?;
+        }
+        let mut features = Vec::new();
+        if let Some(color_scheme) = self.color_scheme {
+            features.push(MediaFeature::new("prefers-color-scheme", color_scheme.as_str()));
+        }
+        if let Some(reduced_motion) = self.reduced_motion {
+            features.push(MediaFeature::new("prefers-reduced-motion", reduced_motion.as_str()));
+        }
+        if !features.is_empty() {
+            dev_tools
+                .execute_cdp_command(&SetEmulatedMedia {
+                    media: None,
+                    features: Some(features),
+                })
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Clear all emulation settings applied with [`Emulation::apply()`].
+    pub async fn clear(dev_tools: &ChromeDevTools) -> WebDriverResult<()> {
+        dev_tools.execute_cdp_command(&ClearDeviceMetricsOverride {}).await?;
+        dev_tools
+            .execute_cdp_command(&SetTouchEmulationEnabled {
+                enabled: false,
+                max_touch_points: None,
+            })
+            .await?;
+        // An empty user agent removes the override.
+        dev_tools.execute_cdp_command(&SetUserAgentOverride::default()).await?;
+        dev_tools.execute_cdp_command(&ClearGeolocationOverride {}).await?;
+        // Revoke the geolocation permission granted by `apply()`.
+        if let Err(e) = dev_tools.execute_cdp("Browser.resetPermissions").await {
+            log::debug!("emulation: unable to reset permissions: {}", e);
+        }
+        dev_tools.execute_cdp_command(&SetTimezoneOverride::default()).await?;
+        dev_tools.execute_cdp_command(&SetLocaleOverride::default()).await?;
+        dev_tools
+            .execute_cdp_command(&SetEmulatedMedia {
+                media: Some(String::new()),
+                features: Some(Vec::new()),
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Apply these settings to the capabilities for a new Chromium-based session.
+    ///
+    /// The viewport, touch support and user agent are set via the `mobileEmulation`
+    /// option, and the locale, color scheme and reduced motion via command-line
+    /// arguments. Geolocation and timezone have no capability equivalent and are
+    /// ignored here; apply them with [`SessionHandle::emulate()`] once the session exists.
+    pub fn apply_to_capabilities(&self, caps: &mut ChromeCapabilities) -> WebDriverResult<()> {
+        match (self.device_metrics(), &self.user_agent) {
+            (Some(metrics), user_agent) => {
+                let device_metrics = MobileDeviceMetrics {
+                    width: metrics.width,
+                    height: metrics.height,
+                    pixel_ratio: metrics.device_scale_factor,
+                    touch: self.touch.unwrap_or(metrics.mobile),
+                    mobile: metrics.mobile,
+                };
+                caps.set_mobile_emulation(MobileEmulation::metrics(
+                    device_metrics,
+                    user_agent.as_deref(),
+                ))?;
+            }
+            (None, Some(user_agent)) => {
+                caps.set_mobile_emulation(MobileEmulation::user_agent(user_agent))?;
+            }
+            (None, None) => {}
+        }
+        if let Some(locale) = &self.locale {
+            caps.add_chrome_arg(&format!("--lang={}", locale))?;
+        }
+        if self.color_scheme == Some(ColorScheme::Dark) {
+            caps.add_chrome_arg("--force-dark-mode")?;
+        }
+        if self.reduced_motion == Some(ReducedMotion::Reduce) {
+            caps.add_chrome_arg("--force-prefers-reduced-motion")?;
+        }
+        if self.geolocation.is_some() || self.timezone.is_some() {
+            log::warn!(
+                "emulation: geolocation and timezone cannot be set via capabilities and were ignored"
+            );
+        }
+        Ok(())
+    }
+}
+
+impl SessionHandle {
+    /// Apply the specified emulation settings to this session. Settings that are not set
+    /// are left unchanged.
+    ///
+    /// This requires a Chromium-based browser. For other browsers, or to emulate a device
+    /// from the start of the session, see [`Emulation::apply_to_capabilities()`].
+    pub async fn emulate(&self, emulation: &Emulation) -> WebDriverResult<()> {
+        emulation.apply(&self.chrome_dev_tools_for_emulation()?).await
+    }
+
+    /// Clear all emulation settings applied with [`SessionHandle::emulate()`].
+    pub async fn clear_emulation(&self) -> WebDriverResult<()> {
+        Emulation::clear(&self.chrome_dev_tools_for_emulation()?).await
+    }
+
+    fn chrome_dev_tools_for_emulation(&self) -> WebDriverResult<ChromeDevTools> {
+        if !self.is_chromium() {
+            let caps = self.config.get_capabilities();
+            let browser = caps.get("browserName").and_then(Value::as_str).unwrap_or_default();
+            return Err(WebDriverError::CustomError(format!(
+                "emulation of a running session is not supported for {}; \
+                 use Emulation::apply_to_capabilities() instead",
+                browser
+            )));
+        }
+        Ok(ChromeDevTools::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CapabilitiesHelper;
+
+    #[test]
+    fn device_capabilities() {
+        assert!(Emulation::device("unknown").is_err());
+
+        let emulation = Emulation::device("pixel 5").unwrap().with_color_scheme(ColorScheme::Dark);
+        let mut caps = ChromeCapabilities::new();
+        emulation.apply_to_capabilities(&mut caps).unwrap();
+        let options = caps.get("goog:chromeOptions").unwrap();
+        assert_eq!(
+            options["mobileEmulation"]["deviceMetrics"],
+            json!({"width": 393, "height": 851, "pixelRatio": 2.75, "mobile": true, "touch": true})
+        );
+        assert!(options["mobileEmulation"]["userAgent"].as_str().unwrap().contains("Pixel 5"));
+        assert_eq!(options["args"], json!(["--force-dark-mode"]));
+
+        let emulation = Emulation::new().with_user_agent("test-agent");
+        let mut caps = ChromeCapabilities::new();
+        emulation.apply_to_capabilities(&mut caps).unwrap();
+        assert_eq!(
+            caps.get("goog:chromeOptions").unwrap(),
+            &json!({"mobileEmulation": {"userAgent": "test-agent"}})
+        );
+    }
+
+    #[test]
+    fn metrics_without_viewport() {
+        let emulation = Emulation::new().with_device_scale_factor(2.0).with_mobile(true);
+        let mut caps = ChromeCapabilities::new();
+        emulation.apply_to_capabilities(&mut caps).unwrap();
+        assert_eq!(
+            caps.get("goog:chromeOptions").unwrap()["mobileEmulation"]["deviceMetrics"],
+            json!({"width": 0, "height": 0, "pixelRatio": 2.0, "mobile": true, "touch": true})
+        );
+
+        // The order of the builder calls does not matter.
+        let emulation = Emulation::new().with_device_scale_factor(3.0).with_viewport(390, 844);
+        assert_eq!(
+            emulation.device_metrics(),
+            Some(DeviceMetrics {
+                width: 390,
+                height: 844,
+                device_scale_factor: 3.0,
+                mobile: false,
+            })
+        );
+        assert_eq!(Emulation::new().with_touch(true).device_metrics(), None);
+    }
+}
@@ -10,13 +10,17 @@ use tokio::io::AsyncWriteExt;
 use fantoccini::cookies::Cookie;
 use fantoccini::elements::Element;
 use fantoccini::error::CmdError;
+use fantoccini::key::Key;
 use fantoccini::wd::{Capabilities, TimeoutConfiguration, WebDriverStatus, WindowHandle};
 
 use crate::action_chain::ActionChain;
+use crate::bidi::BiDiSession;
+use crate::common::command::W3CCommand;
 use crate::common::config::WebDriverConfig;
 use crate::error::{WebDriverError, WebDriverResult};
 use crate::session::scriptret::ScriptRet;
-use crate::{By, Rect, SessionId, SwitchTo, WebElement};
+use crate::support::sleep;
+use crate::{By, PrintOptions, Rect, SessionId, SwitchTo, WebElement};
 
 /// The SessionHandle contains a shared reference to the [`WebDriverConfig`] as well
 /// as the [`fantoccini::Client`] to allow sending commands to the underlying WebDriver.
@@ -54,6 +58,73 @@ impl SessionHandle {
         self.config.get_capabilities()
     }
 
+    /// Get the primary modifier key for the browser's platform. This is `Key::Meta`
+    /// (Cmd) on macOS and iOS, and `Key::Control` everywhere else.
+    ///
+    /// The `platformName` capability is used if it names a specific platform.
+    /// Otherwise the platform is read from the browser's `navigator` object.
+    pub async fn primary_modifier(&self) -> WebDriverResult<Key> {
+        let is_apple = |platform: &str| {
+            let platform = platform.to_lowercase();
+            ["mac", "darwin", "ios", "iphone", "ipad"].iter().any(|p| platform.contains(p))
+        };
+
+        let caps = self.config.get_capabilities();
+        let platform = caps.get("platformName").and_then(Value::as_str).unwrap_or_default();
+        let platform = if platform.is_empty() || platform.eq_ignore_ascii_case("any") {
+            let ret = self
+                .execute_script(
+                    r#"return (navigator.userAgentData && navigator.userAgentData.platform) || navigator.platform;"#,
+                    Vec::new(),
+                )
+                .await?;
+            ret.convert::<String>()?
+        } else {
+            platform.to_string()
+        };
+
+        Ok(if is_apple(&platform) {
+            Key::Meta
+        } else {
+            Key::Control
+        })
+    }
+
+    /// Get the capabilities of the running session, as returned by the driver.
+    ///
+    /// This uses `GET /session/{id}`, which is not part of the W3C spec and is not
+    /// supported by all drivers.
+    pub(crate) async fn session_capabilities(&self) -> WebDriverResult<Value> {
+        let v = self.client.issue_cmd(W3CCommand::GetSessionCapabilities).await?;
+        Ok(match v.get("capabilities") {
+            Some(caps) => caps.clone(),
+            None => v,
+        })
+    }
+
+    /// Return true if the requested browser is Chromium-based, and therefore supports CDP.
+    pub(crate) fn is_chromium(&self) -> bool {
+        let caps = self.config.get_capabilities();
+        let browser = caps.get("browserName").and_then(Value::as_str).unwrap_or_default();
+        ["chrome", "chromium", "msedge", "microsoftedge"].contains(&browser.to_lowercase().as_str())
+    }
+
+    /// Open a WebDriver BiDi connection for this session.
+    ///
+    /// The session must have been created with the `webSocketUrl` capability, e.g. via
+    /// `caps.set_web_socket_url(true)`. See the [bidi](crate::bidi) module for details.
+    ///
+    /// ```ignore
+    /// use thirtyfour::bidi::log;
+    ///
+    /// let bidi = driver.bidi().await?;
+    /// let mut entries = bidi.events_of::<log::EntryAdded>();
+    /// bidi.subscribe(&["log.entryAdded"]).await?;
+    /// ```
+    pub async fn bidi(&self) -> WebDriverResult<BiDiSession> {
+        BiDiSession::connect_to_session(self).await
+    }
+
     /// Get the session ID.
     pub async fn session_id(&self) -> WebDriverResult<SessionId> {
         let id = self.client.session_id().await?;
@@ -1085,6 +1156,72 @@ impl SessionHandle {
         Ok(())
     }
 
+    /// Print the current page and return it as PDF bytes.
+    ///
+    /// ```ignore
+    /// use thirtyfour::{PrintOptions, PrintPage};
+    /// let mut options = PrintOptions::new();
+    /// options.page = PrintPage::a4();
+    /// let pdf = driver.print_page(options).await?;
+    /// assert!(pdf.starts_with(b"%PDF"));
+    /// ```
+    pub async fn print_page(&self, options: PrintOptions) -> WebDriverResult<Vec<u8>> {
+        let src = self.client.issue_cmd(W3CCommand::Print(options)).await?;
+        if let Some(src) = src.as_str() {
+            base64::decode(src)
+                .map_err(|x| WebDriverError::CustomError(format!("unable to decode PDF: {}", x)))
+        } else {
+            Err(WebDriverError::CmdError(CmdError::NotW3C(src)))
+        }
+    }
+
+    /// Print the current page and write the PDF to the specified filename.
+    pub async fn print_to_file(&self, path: &Path, options: PrintOptions) -> WebDriverResult<()> {
+        let pdf = self.print_page(options).await?;
+        let mut file = File::create(path).await?;
+        file.write_all(&pdf).await?;
+        Ok(())
+    }
+
+    /// Save a screenshot to the configured `screenshot.on_failure_dir`, if any.
+    ///
+    /// This is called while another error is being returned, so any error that occurs
+    /// while taking the screenshot is logged rather than returned.
+    pub(crate) async fn save_failure_screenshot(&self, label: &str) {
+        let dir = match self.config.get_settings().screenshot.on_failure_dir {
+            Some(x) => x,
+            None => return,
+        };
+
+        let label: String = label
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let filename =
+            format!("{}_{}.png", chrono::Local::now().format("%Y%m%d_%H%M%S%.3f"), label);
+        let path = dir.join(filename);
+        let result = async {
+            tokio::fs::create_dir_all(&dir).await?;
+            self.screenshot(&path).await
+        };
+        if let Err(e) = result.await {
+            log::warn!("failed to save screenshot to {}: {}", path.display(), e);
+        }
+    }
+
+    /// Sleep for the configured `interaction.slow_motion_ms` delay, if any.
+    pub(crate) async fn slow_motion(&self) {
+        if let Some(delay) = self.config.get_settings().interaction.slow_motion() {
+            sleep(delay).await;
+        }
+    }
+
     /// Return a SwitchTo struct for switching to another window or frame.
     pub fn switch_to(&self) -> SwitchTo {
         SwitchTo::new(self.clone())
@@ -0,0 +1,741 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::StreamExt;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+use tokio::task::{JoinHandle, JoinSet};
+
+use crate::error::WebDriverResult;
+use crate::extensions::chrome::cdp::network::{self, ResourceTiming};
+use crate::extensions::chrome::cdp::{CdpConnection, CdpEvent};
+use crate::extensions::chrome::ChromeDevTools;
+use crate::session::handle::SessionHandle;
+use crate::session::interception::url_matches;
+
+/// Options for a [`HarRecorder`].
+#[derive(Debug, Clone, Default)]
+pub struct HarOptions {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    capture_bodies: bool,
+    max_body_size: Option<usize>,
+}
+
+impl HarOptions {
+    /// Record all requests, without response bodies.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only record requests whose URL matches the pattern (or any other included pattern).
+    /// In the pattern, `*` matches zero or more characters.
+    pub fn include(mut self, pattern: &str) -> Self {
+        self.include.push(pattern.to_string());
+        self
+    }
+
+    /// Do not record requests whose URL matches the pattern.
+    pub fn exclude(mut self, pattern: &str) -> Self {
+        self.exclude.push(pattern.to_string());
+        self
+    }
+
+    /// Set whether to record response bodies.
+    pub fn with_bodies(mut self, capture_bodies: bool) -> Self {
+        self.capture_bodies = capture_bodies;
+        self
+    }
+
+    /// Omit response bodies larger than this many bytes.
+    ///
+    /// This limits what is stored in the HAR, not what is transferred: the size of a
+    /// body is only known once it has been fetched from the browser.
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = Some(max_body_size);
+        self
+    }
+
+    fn should_record(&self, url: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|p| url_matches(p, url)))
+            && !self.exclude.iter().any(|p| url_matches(p, url))
+    }
+}
+
+/// A HAR 1.2 archive. See [http://www.softwareishard.com/blog/har-12-spec/](http://www.softwareishard.com/blog/har-12-spec/).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Har {
+    pub log: HarLog,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HarLog {
+    pub version: String,
+    pub creator: HarCreator,
+    pub entries: Vec<HarEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HarCreator {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    /// ISO 8601 date and time the request started.
+    pub started_date_time: String,
+    /// Total time in milliseconds.
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    pub cache: HarCache,
+    pub timings: HarTimings,
+    #[serde(rename = "serverIPAddress", skip_serializing_if = "Option::is_none")]
+    pub server_ip_address: Option<String>,
+    #[serde(rename = "_resourceType", skip_serializing_if = "Option::is_none")]
+    pub resource_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    pub http_version: String,
+    pub cookies: Vec<HarCookie>,
+    pub headers: Vec<HarHeader>,
+    pub query_string: Vec<HarHeader>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<HarPostData>,
+    /// -1 if unknown.
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    /// 0 if the request failed.
+    pub status: u16,
+    pub status_text: String,
+    pub http_version: String,
+    pub cookies: Vec<HarCookie>,
+    pub headers: Vec<HarHeader>,
+    pub content: HarContent,
+    #[serde(rename = "redirectURL")]
+    pub redirect_url: String,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+/// A header or query string parameter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarHeader {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarCookie {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarPostData {
+    pub mime_type: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    pub size: i64,
+    pub mime_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// `base64` if `text` is base64-encoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarCache {}
+
+/// Request phase timings in milliseconds. -1 means the phase does not apply.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HarTimings {
+    pub blocked: f64,
+    pub dns: f64,
+    pub connect: f64,
+    pub send: f64,
+    pub wait: f64,
+    pub receive: f64,
+    /// Included in `connect`.
+    pub ssl: f64,
+}
+
+impl HarTimings {
+    fn new(timing: Option<&ResourceTiming>, start: f64, end: Option<f64>) -> Self {
+        let t = match timing {
+            Some(t) => t,
+            None => {
+                let total = end.map(|end| ((end - start) * 1000.0).max(0.0)).unwrap_or(0.0);
+                return Self {
+                    blocked: -1.0,
+                    dns: -1.0,
+                    connect: -1.0,
+                    send: 0.0,
+                    wait: total,
+                    receive: 0.0,
+                    ssl: -1.0,
+                };
+            }
+        };
+
+        let span = |start: f64, end: f64| {
+            if start >= 0.0 && end >= start {
+                end - start
+            } else {
+                -1.0
+            }
+        };
+        let queued = ((t.request_time - start) * 1000.0).max(0.0);
+        let first_phase =
+            [t.dns_start, t.connect_start, t.send_start].into_iter().find(|v| *v >= 0.0);
+        Self {
+            blocked: queued + first_phase.unwrap_or(0.0),
+            dns: span(t.dns_start, t.dns_end),
+            connect: span(t.connect_start, t.connect_end),
+            send: span(t.send_start, t.send_end).max(0.0),
+            wait: (t.receive_headers_end - t.send_end).max(0.0),
+            receive: end
+                .map(|end| ((end - t.request_time) * 1000.0 - t.receive_headers_end).max(0.0))
+                .unwrap_or(0.0),
+            ssl: span(t.ssl_start, t.ssl_end),
+        }
+    }
+
+    fn total(&self) -> f64 {
+        [self.blocked, self.dns, self.connect, self.send, self.wait, self.receive]
+            .iter()
+            .filter(|v| **v > 0.0)
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Body {
+    text: Option<String>,
+    base64: bool,
+    size: usize,
+    comment: Option<String>,
+}
+
+/// A request being recorded.
+#[derive(Debug, Clone)]
+struct PendingEntry {
+    wall_time: f64,
+    start: f64,
+    end: Option<f64>,
+    request: network::Request,
+    resource_type: Option<String>,
+    response: Option<network::Response>,
+    encoded_data_length: Option<f64>,
+    redirect_url: String,
+    body: Option<Body>,
+    error: Option<String>,
+}
+
+impl PendingEntry {
+    fn to_har(&self) -> HarEntry {
+        let started = UNIX_EPOCH + Duration::from_secs_f64(self.wall_time.max(0.0));
+        let timings = HarTimings::new(
+            self.response.as_ref().and_then(|r| r.timing.as_ref()),
+            self.start,
+            self.end,
+        );
+        let http_version = http_version(self.response.as_ref().and_then(|r| r.protocol.as_deref()));
+
+        let query_string = url::Url::parse(&self.request.url)
+            .map(|url| {
+                url.query_pairs()
+                    .map(|(name, value)| HarHeader {
+                        name: name.into_owned(),
+                        value: value.into_owned(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let post_data = self.request.post_data.as_ref().map(|text| HarPostData {
+            mime_type: header(&self.request.headers, "content-type").unwrap_or_default(),
+            text: text.clone(),
+        });
+
+        let response = match &self.response {
+            Some(response) => {
+                let body_size = self.encoded_data_length.unwrap_or(response.encoded_data_length);
+                HarResponse {
+                    status: response.status,
+                    status_text: response.status_text.clone(),
+                    http_version: http_version.clone(),
+                    cookies: Vec::new(),
+                    headers: headers(&response.headers),
+                    content: HarContent {
+                        size: self.body.as_ref().map(|b| b.size as i64).unwrap_or(body_size as i64),
+                        mime_type: response.mime_type.clone(),
+                        text: self.body.as_ref().and_then(|b| b.text.clone()),
+                        encoding: self
+                            .body
+                            .as_ref()
+                            .filter(|b| b.base64 && b.text.is_some())
+                            .map(|_| "base64".to_string()),
+                        comment: self.body.as_ref().and_then(|b| b.comment.clone()),
+                    },
+                    redirect_url: self.redirect_url.clone(),
+                    headers_size: -1,
+                    body_size: body_size as i64,
+                }
+            }
+            None => HarResponse {
+                status: 0,
+                status_text: String::new(),
+                http_version: http_version.clone(),
+                cookies: Vec::new(),
+                headers: Vec::new(),
+                content: HarContent {
+                    size: 0,
+                    mime_type: String::new(),
+                    text: None,
+                    encoding: None,
+                    comment: None,
+                },
+                redirect_url: String::new(),
+                headers_size: -1,
+                body_size: -1,
+            },
+        };
+
+        HarEntry {
+            started_date_time: DateTime::<Utc>::from(started)
+                .to_rfc3339_opts(SecondsFormat::Millis, true),
+            time: timings.total(),
+            request: HarRequest {
+                method: self.request.method.clone(),
+                url: self.request.url.clone(),
+                http_version,
+                cookies: Vec::new(),
+                headers: headers(&self.request.headers),
+                query_string,
+                body_size: self.request.post_data.as_ref().map(|d| d.len() as i64).unwrap_or(0),
+                post_data,
+                headers_size: -1,
+            },
+            response,
+            cache: HarCache::default(),
+            timings,
+            server_ip_address: self.response.as_ref().and_then(|r| r.remote_ip_address.clone()),
+            resource_type: self.resource_type.clone(),
+            comment: self.error.clone(),
+        }
+    }
+}
+
+fn headers(headers: &HashMap<String, String>) -> Vec<HarHeader> {
+    let mut headers: Vec<HarHeader> = headers
+        .iter()
+        .map(|(name, value)| HarHeader {
+            name: name.clone(),
+            value: value.clone(),
+        })
+        .collect();
+    headers.sort_by(|a, b| a.name.cmp(&b.name));
+    headers
+}
+
+fn header(headers: &HashMap<String, String>, name: &str) -> Option<String> {
+    headers.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v.clone())
+}
+
+fn http_version(protocol: Option<&str>) -> String {
+    match protocol {
+        Some("h2") => "HTTP/2.0".to_string(),
+        Some("h3") => "HTTP/3".to_string(),
+        Some(p) if !p.is_empty() => p.to_uppercase(),
+        _ => "HTTP/1.1".to_string(),
+    }
+}
+
+/// The recorded requests, in the order they started.
+#[derive(Debug, Default)]
+struct HarState {
+    entries: Vec<PendingEntry>,
+    /// The index of the latest entry for each request id (redirects reuse the id).
+    current: HashMap<String, usize>,
+}
+
+impl HarState {
+    fn current_mut(&mut self, request_id: &str) -> Option<&mut PendingEntry> {
+        let index = *self.current.get(request_id)?;
+        self.entries.get_mut(index)
+    }
+
+    /// Update the state with the specified event. Returns true if the request has
+    /// finished loading, and its body can be fetched.
+    fn handle_event(&mut self, event: &CdpEvent, options: &HarOptions) -> bool {
+        if let Some(Ok(sent)) = event.parse::<network::RequestWillBeSent>() {
+            if let Some(redirect) = sent.redirect_response {
+                if let Some(entry) = self.current_mut(&sent.request_id) {
+                    entry.redirect_url = sent.request.url.clone();
+                    entry.encoded_data_length = Some(redirect.encoded_data_length);
+                    entry.response = Some(redirect);
+                    entry.end = Some(sent.timestamp);
+                }
+                self.current.remove(&sent.request_id);
+            }
+            if options.should_record(&sent.request.url) {
+                self.current.insert(sent.request_id.clone(), self.entries.len());
+                self.entries.push(PendingEntry {
+                    wall_time: sent.wall_time,
+                    start: sent.timestamp,
+                    end: None,
+                    request: sent.request,
+                    resource_type: sent.resource_type,
+                    response: None,
+                    encoded_data_length: None,
+                    redirect_url: String::new(),
+                    body: None,
+                    error: None,
+                });
+            }
+        } else if let Some(Ok(received)) = event.parse::<network::ResponseReceived>() {
+            if let Some(entry) = self.current_mut(&received.request_id) {
+                entry.response = Some(received.response);
+            }
+        } else if let Some(Ok(finished)) = event.parse::<network::LoadingFinished>() {
+            if let Some(entry) = self.current_mut(&finished.request_id) {
+                entry.end = Some(finished.timestamp);
+                entry.encoded_data_length = Some(finished.encoded_data_length);
+                return options.capture_bodies;
+            }
+        } else if let Some(Ok(failed)) = event.parse::<network::LoadingFailed>() {
+            if let Some(entry) = self.current_mut(&failed.request_id) {
+                entry.end = Some(failed.timestamp);
+                entry.error = Some(failed.error_text);
+            }
+        }
+        false
+    }
+
+    fn to_har(&self) -> Har {
+        Har {
+            log: HarLog {
+                version: "1.2".to_string(),
+                creator: HarCreator {
+                    name: "thirtyfour".to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                },
+                entries: self.entries.iter().map(PendingEntry::to_har).collect(),
+            },
+        }
+    }
+}
+
+/// Records a session's network traffic and exports it as a HAR 1.2 archive.
+///
+/// Recording uses CDP `Network` events, so only Chromium-based browsers are supported,
+/// and it stops when the `HarRecorder` is dropped.
+///
+/// # Example:
+/// ```ignore
+/// use thirtyfour::session::har::HarOptions;
+///
+/// let options = HarOptions::new()
+///     .exclude("*.png")
+///     .with_bodies(true)
+///     .with_max_body_size(100_000);
+/// let recorder = driver.record_har(options).await?;
+/// driver.get("http://webappdemo").await?;
+/// recorder.write("webappdemo.har").await?;
+/// ```
+pub struct HarRecorder {
+    connection: CdpConnection,
+    state: Arc<Mutex<HarState>>,
+    /// The response bodies still being fetched. These are aborted when dropped.
+    fetches: Arc<Mutex<JoinSet<()>>>,
+    task: JoinHandle<()>,
+}
+
+impl Drop for HarRecorder {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl HarRecorder {
+    /// Start recording the network traffic of the specified session.
+    pub async fn new(handle: SessionHandle, options: HarOptions) -> WebDriverResult<Self> {
+        let connection = ChromeDevTools::new(handle).connect().await?;
+        Self::with_cdp(connection, options).await
+    }
+
+    /// Start recording network traffic using the specified CDP connection.
+    pub async fn with_cdp(connection: CdpConnection, options: HarOptions) -> WebDriverResult<Self> {
+        let state = Arc::new(Mutex::new(HarState::default()));
+        let fetches = Arc::new(Mutex::new(JoinSet::new()));
+        let mut events = connection.events();
+        let task_state = state.clone();
+        let task_fetches = fetches.clone();
+        let task_connection = connection.clone();
+        let task = tokio::spawn(async move {
+            while let Some(event) = events.next().await {
+                if !task_state.lock().handle_event(&event, &options) {
+                    continue;
+                }
+                // The request has finished, so fetch the body in the background.
+                let request_id = event.params["requestId"].as_str().unwrap_or_default().to_string();
+                let state = task_state.clone();
+                let connection = task_connection.clone();
+                let max_body_size = options.max_body_size;
+                let mut fetches = task_fetches.lock();
+                while fetches.try_join_next().is_some() {}
+                fetches.spawn(async move {
+                    let body = fetch_body(&connection, &request_id, max_body_size).await;
+                    if let Some(entry) = state.lock().current_mut(&request_id) {
+                        entry.body = Some(body);
+                    }
+                });
+            }
+        });
+
+        connection.execute_cdp_command(&network::Enable::default()).await?;
+        Ok(Self {
+            connection,
+            state,
+            fetches,
+            task,
+        })
+    }
+
+    /// Get the traffic recorded so far. Bodies that are still being fetched are not
+    /// included; use `write()` or `stop()` to wait for them.
+    pub fn har(&self) -> Har {
+        self.state.lock().to_har()
+    }
+
+    /// Write the traffic recorded so far to the specified path, as HAR JSON.
+    pub async fn write<P: AsRef<Path>>(&self, path: P) -> WebDriverResult<()> {
+        self.wait_for_bodies().await;
+        let json = serde_json::to_vec_pretty(&self.har())?;
+        tokio::fs::write(path, json).await?;
+        Ok(())
+    }
+
+    /// Discard the traffic recorded so far.
+    pub fn clear(&self) {
+        let mut state = self.state.lock();
+        state.entries.clear();
+        state.current.clear();
+    }
+
+    /// Stop recording and return the recorded traffic.
+    pub async fn stop(self) -> WebDriverResult<Har> {
+        self.task.abort();
+        self.wait_for_bodies().await;
+        self.connection.execute_cdp_command(&network::Disable::default()).await?;
+        Ok(self.har())
+    }
+
+    /// Wait for the response bodies that are currently being fetched.
+    async fn wait_for_bodies(&self) {
+        let mut fetches = std::mem::take(&mut *self.fetches.lock());
+        while fetches.join_next().await.is_some() {}
+    }
+}
+
+async fn fetch_body(
+    connection: &CdpConnection,
+    request_id: &str,
+    max_body_size: Option<usize>,
+) -> Body {
+    let command = network::GetResponseBody {
+        request_id: request_id.to_string(),
+    };
+    let ret = match connection.execute_cdp_command(&command).await {
+        Ok(ret) => ret,
+        Err(e) => {
+            return Body {
+                text: None,
+                base64: false,
+                size: 0,
+                comment: Some(format!("body not available: {}", e)),
+            }
+        }
+    };
+
+    let size = if ret.base64_encoded {
+        ret.body.len() / 4 * 3 - ret.body.chars().rev().take_while(|c| *c == '=').count()
+    } else {
+        ret.body.len()
+    };
+    match max_body_size {
+        Some(max) if size > max => Body {
+            text: None,
+            base64: ret.base64_encoded,
+            size,
+            comment: Some(format!("body omitted: {} bytes is larger than {} bytes", size, max)),
+        },
+        _ => Body {
+            text: Some(ret.body),
+            base64: ret.base64_encoded,
+            size,
+            comment: None,
+        },
+    }
+}
+
+impl SessionHandle {
+    /// Start recording network traffic as a HAR archive. See [`HarRecorder`] for details.
+    pub async fn record_har(&self, options: HarOptions) -> WebDriverResult<HarRecorder> {
+        HarRecorder::new(self.clone(), options).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(method: &str, params: serde_json::Value) -> CdpEvent {
+        CdpEvent {
+            method: method.to_string(),
+            params,
+            session_id: None,
+        }
+    }
+
+    #[test]
+    fn record_entries() {
+        let options = HarOptions::new().exclude("*.png").with_bodies(true);
+        let mut state = HarState::default();
+        let request =
+            |url: &str| json!({"url": url, "method": "GET", "headers": {"Accept": "*/*"}});
+        let response = |url: &str, status: u16| {
+            json!({
+                "url": url, "status": status, "statusText": "", "headers": {}, "mimeType": "text/html",
+                "protocol": "h2", "encodedDataLength": 100.0
+            })
+        };
+
+        let events = [
+            event(
+                "Network.requestWillBeSent",
+                json!({"requestId": "1", "request": request("http://a.test/?q=1"), "timestamp": 10.0, "wallTime": 1600000000.0}),
+            ),
+            event(
+                "Network.requestWillBeSent",
+                json!({"requestId": "2", "request": request("http://a.test/logo.png"), "timestamp": 10.1, "wallTime": 1600000000.1}),
+            ),
+            event(
+                "Network.requestWillBeSent",
+                json!({
+                    "requestId": "1", "request": request("http://b.test/"), "timestamp": 10.2, "wallTime": 1600000000.2,
+                    "redirectResponse": response("http://a.test/?q=1", 302)
+                }),
+            ),
+            event(
+                "Network.responseReceived",
+                json!({"requestId": "1", "timestamp": 10.3, "response": response("http://b.test/", 200)}),
+            ),
+        ];
+        for e in &events {
+            assert!(!state.handle_event(e, &options));
+        }
+        let finished = event(
+            "Network.loadingFinished",
+            json!({"requestId": "1", "timestamp": 10.5, "encodedDataLength": 500.0}),
+        );
+        assert!(state.handle_event(&finished, &options));
+
+        let har = state.to_har();
+        let entries = &har.log.entries;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].started_date_time, "2020-09-13T12:26:40.000Z");
+        assert_eq!(entries[0].request.query_string[0].value, "1");
+        assert_eq!(entries[0].response.status, 302);
+        assert_eq!(entries[0].response.redirect_url, "http://b.test/");
+        assert_eq!(entries[1].response.status, 200);
+        assert_eq!(entries[1].response.http_version, "HTTP/2.0");
+        assert_eq!(entries[1].response.body_size, 500);
+        assert!((entries[1].time - 300.0).abs() < 1e-6);
+    }
+
+    /// Send a finished request once `Network.enable` is received, and answer
+    /// `Network.getResponseBody` slowly.
+    async fn slow_body_stand_in(listener: tokio::net::TcpListener) {
+        use futures::SinkExt;
+        use tokio_tungstenite::tungstenite::Message;
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        while let Some(Ok(Message::Text(text))) = ws.next().await {
+            let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+            let mut messages = Vec::new();
+            match request["method"].as_str().unwrap_or_default() {
+                "Network.enable" => {
+                    messages.push(json!({"id": request["id"], "result": {}}));
+                    messages.push(json!({"method": "Network.requestWillBeSent", "params": {
+                        "requestId": "1", "timestamp": 1.0, "wallTime": 1600000000.0,
+                        "request": {"url": "http://a.test/", "method": "GET", "headers": {}}
+                    }}));
+                    messages.push(json!({"method": "Network.responseReceived", "params": {
+                        "requestId": "1", "timestamp": 1.5, "response": {
+                            "url": "http://a.test/", "status": 200, "statusText": "OK",
+                            "headers": {}, "mimeType": "text/plain", "encodedDataLength": 10.0
+                        }
+                    }}));
+                    messages.push(json!({"method": "Network.loadingFinished", "params": {
+                        "requestId": "1", "timestamp": 2.0, "encodedDataLength": 10.0
+                    }}));
+                }
+                "Network.getResponseBody" => {
+                    crate::support::sleep(Duration::from_millis(200)).await;
+                    messages.push(json!({"id": request["id"], "result": {
+                        "body": "hello", "base64Encoded": false
+                    }}));
+                }
+                _ => messages.push(json!({"id": request["id"], "result": {}})),
+            }
+            for message in messages {
+                ws.send(Message::Text(message.to_string())).await.unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn stop_waits_for_bodies() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(slow_body_stand_in(listener));
+
+        let connection = CdpConnection::connect(&url).await.unwrap();
+        let recorder =
+            HarRecorder::with_cdp(connection, HarOptions::new().with_bodies(true)).await.unwrap();
+        // Wait for the request to finish loading, so that its body is being fetched.
+        while recorder.fetches.lock().is_empty() {
+            crate::support::sleep(Duration::from_millis(10)).await;
+        }
+
+        let har = recorder.stop().await.unwrap();
+        assert_eq!(har.log.entries[0].response.content.text.as_deref(), Some("hello"));
+    }
+}
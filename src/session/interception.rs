@@ -0,0 +1,469 @@
+use futures::StreamExt;
+use parking_lot::RwLock;
+use serde_json::Value;
+use std::fmt;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+use crate::bidi::{self, BiDiEventType, BiDiSession};
+use crate::error::{WebDriverError, WebDriverResult};
+use crate::extensions::chrome::cdp::{self, CdpConnection};
+use crate::extensions::chrome::ChromeDevTools;
+use crate::session::handle::SessionHandle;
+
+/// A request paused by a [`RequestInterceptor`].
+#[derive(Debug, Clone)]
+pub struct InterceptedRequest {
+    pub url: String,
+    pub method: String,
+    pub headers: Vec<(String, String)>,
+    /// The request body, if known.
+    pub post_data: Option<String>,
+    /// The resource type, e.g. `Document`, `XHR` or `Image`. Only available via CDP.
+    pub resource_type: Option<String>,
+}
+
+impl InterceptedRequest {
+    /// Get the value of the specified header (case-insensitive).
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+}
+
+/// A canned response used to fulfill an intercepted request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl MockResponse {
+    /// Create a response with the specified status code and no body.
+    pub fn new(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    /// Add the specified header.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set the response body.
+    pub fn with_body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Set the response body to the specified JSON, with a matching `Content-Type`.
+    pub fn with_json(self, json: &Value) -> Self {
+        self.with_header("Content-Type", "application/json").with_body(json.to_string())
+    }
+}
+
+/// What to do with an intercepted request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InterceptAction {
+    /// Send the request to the server unmodified.
+    Continue,
+    /// Respond with the specified response, without contacting the server.
+    Fulfill(MockResponse),
+    /// Fail the request with a network error.
+    Fail,
+}
+
+type RouteHandler = Arc<dyn Fn(&InterceptedRequest) -> InterceptAction + Send + Sync>;
+
+#[derive(Clone)]
+struct Route {
+    pattern: String,
+    method: Option<String>,
+    handler: RouteHandler,
+}
+
+impl Route {
+    fn matches(&self, request: &InterceptedRequest) -> bool {
+        let method_matches = match &self.method {
+            Some(method) => method.eq_ignore_ascii_case(&request.method),
+            None => true,
+        };
+        method_matches && url_matches(&self.pattern, &request.url)
+    }
+}
+
+/// Return true if the URL matches the pattern, where `*` matches zero or more characters.
+pub(crate) fn url_matches(pattern: &str, url: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let mut rest = match url.strip_prefix(first) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let parts: Vec<&str> = parts.collect();
+    match parts.split_last() {
+        // No wildcard.
+        None => rest.is_empty(),
+        Some((last, middle)) => {
+            for part in middle {
+                match rest.find(part) {
+                    Some(i) => rest = &rest[i + part.len()..],
+                    None => return false,
+                }
+            }
+            rest.ends_with(last)
+        }
+    }
+}
+
+enum Backend {
+    Cdp(CdpConnection),
+    BiDi {
+        session: BiDiSession,
+        intercept: String,
+    },
+}
+
+/// Intercepts network requests, allowing them to be stubbed, blocked or failed.
+///
+/// Requests are matched against the routes added with [`RequestInterceptor::route()`]
+/// and similar methods. If several routes match, the one added last is used. Requests
+/// that match no route are continued unmodified.
+///
+/// Chromium-based browsers are supported via the CDP `Fetch` domain, and other browsers
+/// via WebDriver BiDi (the session must be created with the `webSocketUrl` capability).
+/// Interception stops when the `RequestInterceptor` is dropped, but it is better to
+/// call [`RequestInterceptor::stop()`] so that the browser stops pausing requests.
+///
+/// # Example:
+/// ```ignore
+/// use serde_json::json;
+/// use thirtyfour::session::interception::{InterceptAction, MockResponse};
+///
+/// let interceptor = driver.intercept_requests().await?;
+/// interceptor.fulfill("*/api/user", MockResponse::new(200).with_json(&json!({"name": "test"})));
+/// interceptor.block("*://analytics.example.com/*");
+/// interceptor.route_method("POST", "*/api/orders", |_| {
+///     InterceptAction::Fulfill(MockResponse::new(500))
+/// });
+/// driver.get("http://webappdemo").await?;
+/// // ...
+/// interceptor.stop().await?;
+/// ```
+pub struct RequestInterceptor {
+    routes: Arc<RwLock<Vec<Route>>>,
+    backend: Backend,
+    task: JoinHandle<()>,
+}
+
+impl fmt::Debug for RequestInterceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let patterns: Vec<String> = self.routes.read().iter().map(|r| r.pattern.clone()).collect();
+        f.debug_struct("RequestInterceptor").field("routes", &patterns).finish()
+    }
+}
+
+impl Drop for RequestInterceptor {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl RequestInterceptor {
+    /// Start intercepting requests for the specified session, using CDP on Chromium-based
+    /// browsers where possible and WebDriver BiDi otherwise.
+    pub async fn new(handle: SessionHandle) -> WebDriverResult<Self> {
+        if handle.is_chromium() {
+            match ChromeDevTools::new(handle.clone()).connect().await {
+                Ok(connection) => return Self::with_cdp(connection).await,
+                Err(e) => log::debug!("request interception: CDP is not available: {}", e),
+            }
+        }
+        Self::with_bidi(handle.bidi().await?).await
+    }
+
+    /// Start intercepting requests using the specified CDP connection.
+    pub async fn with_cdp(connection: CdpConnection) -> WebDriverResult<Self> {
+        let routes: Arc<RwLock<Vec<Route>>> = Arc::new(RwLock::new(Vec::new()));
+        let mut paused = connection.events_of::<cdp::fetch::RequestPaused>();
+        let task_routes = routes.clone();
+        let task_connection = connection.clone();
+        let task = tokio::spawn(async move {
+            while let Some(event) = paused.next().await {
+                let mut headers: Vec<(String, String)> =
+                    event.request.headers.into_iter().collect();
+                headers.sort();
+                let request = InterceptedRequest {
+                    url: event.request.url,
+                    method: event.request.method,
+                    headers,
+                    post_data: event.request.post_data,
+                    resource_type: Some(event.resource_type),
+                };
+                let action = handle_request(&task_routes, &request);
+                let connection = task_connection.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = resolve_cdp(&connection, event.request_id, action).await {
+                        log::warn!(
+                            "request interception: unable to resolve {}: {}",
+                            request.url,
+                            e
+                        );
+                    }
+                });
+            }
+        });
+
+        connection.execute_cdp_command(&cdp::fetch::Enable::default()).await?;
+        Ok(Self {
+            routes,
+            backend: Backend::Cdp(connection),
+            task,
+        })
+    }
+
+    /// Start intercepting requests using the specified BiDi session.
+    pub async fn with_bidi(session: BiDiSession) -> WebDriverResult<Self> {
+        let routes: Arc<RwLock<Vec<Route>>> = Arc::new(RwLock::new(Vec::new()));
+        let mut events = session.events_of::<bidi::network::BeforeRequestSent>();
+        let task_routes = routes.clone();
+        let task_session = session.clone();
+        let task = tokio::spawn(async move {
+            while let Some(bidi::network::BeforeRequestSent(event)) = events.next().await {
+                if !event.is_blocked {
+                    continue;
+                }
+                let request = InterceptedRequest {
+                    url: event.request.url,
+                    method: event.request.method,
+                    headers: event
+                        .request
+                        .headers
+                        .iter()
+                        .map(|h| (h.name.clone(), h.value.to_text()))
+                        .collect(),
+                    post_data: None,
+                    resource_type: None,
+                };
+                let action = handle_request(&task_routes, &request);
+                let session = task_session.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = resolve_bidi(&session, event.request.request, action).await {
+                        log::warn!(
+                            "request interception: unable to resolve {}: {}",
+                            request.url,
+                            e
+                        );
+                    }
+                });
+            }
+        });
+
+        session.subscribe(&[bidi::network::BeforeRequestSent::METHOD]).await?;
+        let intercept = session
+            .send(&bidi::network::AddIntercept {
+                phases: vec![bidi::network::InterceptPhase::BeforeRequestSent],
+                ..Default::default()
+            })
+            .await?
+            .intercept;
+        Ok(Self {
+            routes,
+            backend: Backend::BiDi {
+                session,
+                intercept,
+            },
+            task,
+        })
+    }
+
+    /// Handle requests whose URL matches the pattern with the specified handler.
+    /// In the pattern, `*` matches zero or more characters.
+    pub fn route<F>(&self, pattern: &str, handler: F)
+    where
+        F: Fn(&InterceptedRequest) -> InterceptAction + Send + Sync + 'static,
+    {
+        self.add_route(pattern, None, Arc::new(handler));
+    }
+
+    /// Handle requests with the specified HTTP method whose URL matches the pattern.
+    pub fn route_method<F>(&self, method: &str, pattern: &str, handler: F)
+    where
+        F: Fn(&InterceptedRequest) -> InterceptAction + Send + Sync + 'static,
+    {
+        self.add_route(pattern, Some(method.to_string()), Arc::new(handler));
+    }
+
+    /// Respond to requests whose URL matches the pattern with the specified response.
+    pub fn fulfill(&self, pattern: &str, response: MockResponse) {
+        self.route(pattern, move |_| InterceptAction::Fulfill(response.clone()));
+    }
+
+    /// Fail requests whose URL matches the pattern with a network error.
+    pub fn block(&self, pattern: &str) {
+        self.route(pattern, |_| InterceptAction::Fail);
+    }
+
+    /// Remove all routes. All requests will be continued unmodified.
+    pub fn clear_routes(&self) {
+        self.routes.write().clear();
+    }
+
+    fn add_route(&self, pattern: &str, method: Option<String>, handler: RouteHandler) {
+        self.routes.write().push(Route {
+            pattern: pattern.to_string(),
+            method,
+            handler,
+        });
+    }
+
+    /// Stop intercepting requests.
+    pub async fn stop(self) -> WebDriverResult<()> {
+        self.task.abort();
+        match &self.backend {
+            Backend::Cdp(connection) => {
+                connection.execute_cdp_command(&cdp::fetch::Disable::default()).await?;
+            }
+            Backend::BiDi {
+                session,
+                intercept,
+            } => {
+                session
+                    .send(&bidi::network::RemoveIntercept {
+                        intercept: intercept.clone(),
+                    })
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn handle_request(routes: &RwLock<Vec<Route>>, request: &InterceptedRequest) -> InterceptAction {
+    // Clone the handler so that it can add or remove routes itself.
+    let route = routes.read().iter().rev().find(|r| r.matches(request)).cloned();
+    match route {
+        Some(route) => (route.handler)(request),
+        None => InterceptAction::Continue,
+    }
+}
+
+async fn resolve_cdp(
+    connection: &CdpConnection,
+    request_id: String,
+    action: InterceptAction,
+) -> WebDriverResult<()> {
+    match action {
+        InterceptAction::Continue => {
+            connection
+                .execute_cdp_command(&cdp::fetch::ContinueRequest {
+                    request_id,
+                    ..Default::default()
+                })
+                .await?;
+        }
+        InterceptAction::Fulfill(response) => {
+            let headers = response
+                .headers
+                .into_iter()
+                .map(|(name, value)| cdp::fetch::HeaderEntry {
+                    name,
+                    value,
+                })
+                .collect();
+            connection
+                .execute_cdp_command(&cdp::fetch::FulfillRequest {
+                    request_id,
+                    response_code: response.status,
+                    response_headers: Some(headers),
+                    body: Some(base64::encode(&response.body)),
+                    response_phrase: None,
+                })
+                .await?;
+        }
+        InterceptAction::Fail => {
+            connection
+                .execute_cdp_command(&cdp::fetch::FailRequest {
+                    request_id,
+                    error_reason: cdp::fetch::ErrorReason::Failed,
+                })
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn resolve_bidi(
+    session: &BiDiSession,
+    request: String,
+    action: InterceptAction,
+) -> WebDriverResult<()> {
+    match action {
+        InterceptAction::Continue => {
+            session
+                .send(&bidi::network::ContinueRequest {
+                    request,
+                    ..Default::default()
+                })
+                .await?;
+        }
+        InterceptAction::Fulfill(response) => {
+            let headers = response
+                .headers
+                .into_iter()
+                .map(|(name, value)| bidi::network::Header::new(name, value))
+                .collect();
+            session
+                .send(&bidi::network::ProvideResponse {
+                    request,
+                    body: Some(bidi::network::BytesValue::Base64(base64::encode(&response.body))),
+                    headers: Some(headers),
+                    reason_phrase: None,
+                    status_code: Some(response.status),
+                })
+                .await?;
+        }
+        InterceptAction::Fail => {
+            session
+                .send(&bidi::network::FailRequest {
+                    request,
+                })
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+impl SessionHandle {
+    /// Start intercepting network requests. See [`RequestInterceptor`] for details.
+    pub async fn intercept_requests(&self) -> WebDriverResult<RequestInterceptor> {
+        RequestInterceptor::new(self.clone()).await.map_err(|e| {
+            WebDriverError::CustomError(format!(
+                "request interception requires CDP or WebDriver BiDi: {}",
+                e
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_url_patterns() {
+        assert!(url_matches("https://example.com/", "https://example.com/"));
+        assert!(!url_matches("https://example.com/", "https://example.com/a"));
+        assert!(url_matches("*", "https://example.com/a"));
+        assert!(url_matches("*/api/*", "https://example.com/api/users?id=1"));
+        assert!(url_matches("*.png", "https://example.com/logo.png"));
+        assert!(!url_matches("*.png", "https://example.com/logo.png?v=1"));
+        assert!(url_matches("https://*.example.com/*", "https://cdn.example.com/app.js"));
+        assert!(!url_matches("https://*.example.com/*", "https://example.org/app.js"));
+        assert!(!url_matches("*abcabc", "abc"));
+    }
+}
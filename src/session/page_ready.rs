@@ -0,0 +1,262 @@
+use serde::Deserialize;
+use std::future::Future;
+use std::time::Duration;
+
+use crate::error::{WebDriverError, WebDriverResult};
+use crate::query::{ElementPoller, ElementPollerTicker};
+use crate::session::handle::SessionHandle;
+use crate::support::sleep;
+
+/// The settle time used by [`SessionHandle::wait_for_page_ready()`].
+pub const DEFAULT_PAGE_SETTLE_TIME: Duration = Duration::from_millis(500);
+
+/// Installs the activity instrumentation (once per document) and returns the current state.
+///
+/// Activity is a fetch/XHR request starting or finishing, an animation or transition
+/// ending, or a change in `readyState` or in the number of running animations.
+/// Only requests started after the instrumentation was installed can be tracked.
+/// Infinite animations (e.g. decorative loops) are ignored since they never finish.
+const PAGE_STATE_SCRIPT: &str = r#"
+    if (!window.__thirtyfour_page_state) {
+        const state = { pending: 0, lastActivity: Date.now(), readyState: null, animations: 0 };
+        window.__thirtyfour_page_state = state;
+        const touch = () => { state.lastActivity = Date.now(); };
+        const begin = () => { state.pending++; touch(); };
+        const end = () => { state.pending = Math.max(0, state.pending - 1); touch(); };
+
+        document.addEventListener("readystatechange", touch);
+        for (const type of ["animationend", "animationcancel", "transitionend", "transitioncancel"]) {
+            document.addEventListener(type, touch, true);
+        }
+
+        if (window.fetch) {
+            const origFetch = window.fetch;
+            window.fetch = function() {
+                begin();
+                return origFetch.apply(this, arguments).finally(end);
+            };
+        }
+
+        const origSend = XMLHttpRequest.prototype.send;
+        XMLHttpRequest.prototype.send = function() {
+            begin();
+            this.addEventListener("loadend", end, { once: true });
+            return origSend.apply(this, arguments);
+        };
+    }
+
+    const state = window.__thirtyfour_page_state;
+    let animations = 0;
+    if (document.getAnimations) {
+        animations = document.getAnimations().filter(a => {
+            const timing = a.effect ? a.effect.getComputedTiming() : {};
+            return a.playState === "running" && timing.iterations !== Infinity;
+        }).length;
+    }
+    if (state.readyState !== null
+        && (state.readyState !== document.readyState || state.animations !== animations)) {
+        state.lastActivity = Date.now();
+    }
+    state.readyState = document.readyState;
+    state.animations = animations;
+    return {
+        readyState: document.readyState,
+        pendingRequests: state.pending,
+        runningAnimations: animations,
+        idleMs: Date.now() - state.lastActivity
+    };
+"#;
+
+/// Snapshot of the page activity as reported by the injected instrumentation.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageState {
+    /// The value of `document.readyState`.
+    pub ready_state: String,
+    /// The number of fetch/XHR requests currently in flight.
+    pub pending_requests: u64,
+    /// The number of finite CSS animations/transitions currently running.
+    pub running_animations: u64,
+    /// Milliseconds since the last activity: a fetch/XHR request starting or finishing,
+    /// an animation ending, or a change in `readyState` or in the running animations.
+    pub idle_ms: u64,
+}
+
+impl PageState {
+    /// Return true if the document is loaded and there is no network or animation activity.
+    pub fn is_quiet(&self) -> bool {
+        self.ready_state == "complete" && self.pending_requests == 0 && self.running_animations == 0
+    }
+}
+
+impl SessionHandle {
+    /// Get the current page activity, installing the fetch/XHR instrumentation if needed.
+    pub async fn page_state(&self) -> WebDriverResult<PageState> {
+        let ret = self.execute_script(PAGE_STATE_SCRIPT, Vec::new()).await?;
+        ret.convert()
+    }
+
+    /// Wait until the page is ready, using the default settle time of 500ms.
+    ///
+    /// See [`SessionHandle::wait_for_page_ready_with_settle()`] for more details.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// driver.get("http://webappdemo").await?;
+    /// driver.wait_for_page_ready().await?;
+    /// ```
+    pub async fn wait_for_page_ready(&self) -> WebDriverResult<()> {
+        self.wait_for_page_ready_with_settle(DEFAULT_PAGE_SETTLE_TIME).await
+    }
+
+    /// Wait until `document.readyState` is `complete`, there are no outstanding fetch/XHR
+    /// requests and no running CSS animations, and the page has stayed that way for at
+    /// least `settle_time`.
+    ///
+    /// The settle time is measured by the page itself, so a page that is already idle is
+    /// ready immediately. A quiet page that has not been idle for long enough is checked
+    /// again once the remaining settle time has passed, even with `ElementPoller::NoWait`.
+    /// Otherwise polling follows the configured `ElementPoller`, and a `Timeout` error is
+    /// returned if the page does not settle in time.
+    ///
+    /// **NOTE:** Requests are tracked by instrumenting `fetch` and `XMLHttpRequest` from
+    /// the first poll onwards, so requests that started before then are not seen.
+    /// For that reason it is best to call this immediately after `get()` or a click.
+    pub async fn wait_for_page_ready_with_settle(
+        &self,
+        settle_time: Duration,
+    ) -> WebDriverResult<()> {
+        let poller = self.config.get_query_poller();
+        let result = wait_until_settled(poller, settle_time, || self.page_state()).await;
+        if let Err(WebDriverError::Timeout(_)) = &result {
+            self.save_failure_screenshot("page_ready").await;
+        }
+        result
+    }
+}
+
+/// Poll the page state until the page has been quiet for at least `settle_time`.
+///
+/// The decision is based on how long the page itself reports being idle, so a page that
+/// is already idle is ready on the first poll. A change in `readyState` or in the running
+/// animations since the previous poll also counts as activity. If the page is quiet but
+/// has not been idle for long enough, this sleeps for the remaining settle time before
+/// polling again, regardless of the poller interval.
+async fn wait_until_settled<F, Fut>(
+    poller: ElementPoller,
+    settle_time: Duration,
+    mut page_state: F,
+) -> WebDriverResult<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = WebDriverResult<PageState>>,
+{
+    let mut ticker = ElementPollerTicker::new(poller);
+    let mut slept = false;
+    let mut previous: Option<PageState> = None;
+
+    loop {
+        let state = page_state().await?;
+        let changed = previous.replace(state.clone()).is_some_and(|p| {
+            p.ready_state != state.ready_state || p.running_animations != state.running_animations
+        });
+        if state.is_quiet() {
+            let idle = if changed {
+                Duration::ZERO
+            } else {
+                Duration::from_millis(state.idle_ms)
+            };
+            if idle >= settle_time {
+                return Ok(());
+            }
+            // Only sleep once in a row, so a page with constant short bursts of activity
+            // still times out according to the poller.
+            if !slept {
+                sleep(settle_time - idle).await;
+                slept = true;
+                continue;
+            }
+        }
+        slept = false;
+
+        if !ticker.tick().await {
+            return Err(WebDriverError::Timeout(format!(
+                "page not ready: readyState={}, pending requests={}, running animations={}",
+                state.ready_state, state.pending_requests, state.running_animations
+            )));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn state(pending_requests: u64, idle_since: Instant) -> PageState {
+        PageState {
+            ready_state: "complete".to_string(),
+            pending_requests,
+            running_animations: 0,
+            idle_ms: idle_since.elapsed().as_millis() as u64,
+        }
+    }
+
+    #[tokio::test]
+    async fn settles_without_polling() {
+        let settle_time = Duration::from_millis(50);
+
+        // Already idle for longer than the settle time.
+        let idle_since = Instant::now() - Duration::from_millis(100);
+        let mut polls = 0;
+        wait_until_settled(ElementPoller::NoWait, settle_time, || {
+            polls += 1;
+            futures::future::ready(Ok(state(0, idle_since)))
+        })
+        .await
+        .unwrap();
+        assert_eq!(polls, 1);
+
+        // Idle, but not for long enough yet.
+        let idle_since = Instant::now();
+        let start = Instant::now();
+        wait_until_settled(ElementPoller::NoWait, settle_time, || {
+            futures::future::ready(Ok(state(0, idle_since)))
+        })
+        .await
+        .unwrap();
+        assert!(start.elapsed() >= settle_time);
+
+        // Busy.
+        let result = wait_until_settled(ElementPoller::NoWait, settle_time, || {
+            futures::future::ready(Ok(state(1, idle_since)))
+        })
+        .await;
+        assert!(matches!(result, Err(WebDriverError::Timeout(_))));
+    }
+
+    #[tokio::test]
+    async fn animation_end_restarts_settle_time() {
+        let settle_time = Duration::from_millis(50);
+        // The page reports being idle for a long time, but the animation only just ended.
+        let idle_since = Instant::now() - Duration::from_millis(1000);
+        let start = Instant::now();
+        let mut polls = 0;
+        wait_until_settled(
+            ElementPoller::TimeoutWithInterval(Duration::from_secs(1), Duration::from_millis(1)),
+            settle_time,
+            || {
+                polls += 1;
+                let mut state = state(0, idle_since);
+                if polls == 1 {
+                    state.running_animations = 1;
+                }
+                futures::future::ready(Ok(state))
+            },
+        )
+        .await
+        .unwrap();
+        assert!(start.elapsed() >= settle_time);
+        assert_eq!(polls, 3);
+    }
+}
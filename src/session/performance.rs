@@ -0,0 +1,403 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::error::{WebDriverError, WebDriverResult};
+use crate::extensions::chrome::cdp::performance;
+use crate::extensions::chrome::ChromeDevTools;
+use crate::session::handle::SessionHandle;
+
+/// Returns the Navigation Timing, Resource Timing and paint entries of the current page.
+const PERFORMANCE_TIMING_SCRIPT: &str = r#"
+    const toJSON = e => e.toJSON ? e.toJSON() : e;
+    const paint = {};
+    for (const e of performance.getEntriesByType("paint")) paint[e.name] = e.startTime;
+    return {
+        navigation: performance.getEntriesByType("navigation").map(toJSON)[0] || null,
+        resources: performance.getEntriesByType("resource").map(toJSON),
+        firstPaint: "first-paint" in paint ? paint["first-paint"] : null,
+        firstContentfulPaint: "first-contentful-paint" in paint ? paint["first-contentful-paint"] : null
+    };
+"#;
+
+/// Installs the Web Vitals observers (once per document) and returns the current values.
+///
+/// CLS is the largest burst of layout shifts (shifts less than 1s apart, up to 5s in
+/// total), excluding shifts shortly after user input. INP is the longest interaction.
+const WEB_VITALS_SCRIPT: &str = r#"
+    if (!window.__thirtyfour_vitals) {
+        const vitals = { lcp: null, cls: 0, inp: null, observers: [] };
+        window.__thirtyfour_vitals = vitals;
+        let burst = 0, burstStart = 0, burstLast = 0;
+        const onInteraction = e => {
+            if (e.interactionId) vitals.inp = Math.max(vitals.inp || 0, e.duration);
+        };
+        const handlers = {
+            "largest-contentful-paint": e => { vitals.lcp = e.startTime; },
+            "layout-shift": e => {
+                if (e.hadRecentInput) return;
+                if (burst && e.startTime - burstLast < 1000 && e.startTime - burstStart < 5000) {
+                    burst += e.value;
+                } else {
+                    burst = e.value;
+                    burstStart = e.startTime;
+                }
+                burstLast = e.startTime;
+                vitals.cls = Math.max(vitals.cls, burst);
+            },
+            "first-input": onInteraction,
+            "event": onInteraction
+        };
+        for (const [type, handle] of Object.entries(handlers)) {
+            try {
+                const observer = new PerformanceObserver(list => list.getEntries().forEach(handle));
+                const options = { type, buffered: true };
+                if (type === "event") options.durationThreshold = 16;
+                observer.observe(options);
+                vitals.observers.push([observer, handle]);
+            } catch (e) {
+                // The entry type is not supported by this browser.
+            }
+        }
+    }
+
+    const vitals = window.__thirtyfour_vitals;
+    for (const [observer, handle] of vitals.observers) observer.takeRecords().forEach(handle);
+    const navigation = performance.getEntriesByType("navigation")[0];
+    const fcp = performance.getEntriesByName("first-contentful-paint")[0];
+    return {
+        largestContentfulPaint: vitals.lcp,
+        cumulativeLayoutShift: vitals.cls,
+        interactionToNextPaint: vitals.inp,
+        firstContentfulPaint: fcp ? fcp.startTime : null,
+        timeToFirstByte: navigation ? navigation.responseStart : null
+    };
+"#;
+
+/// A Navigation Timing entry for the current document.
+///
+/// All times are in milliseconds relative to the start of the navigation. Fields not
+/// supported by the browser are 0.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NavigationTiming {
+    /// The document URL.
+    pub name: String,
+    /// One of `navigate`, `reload`, `back_forward` or `prerender`.
+    #[serde(rename = "type")]
+    pub navigation_type: String,
+    pub duration: f64,
+    pub redirect_count: u32,
+    pub redirect_start: f64,
+    pub redirect_end: f64,
+    pub fetch_start: f64,
+    pub domain_lookup_start: f64,
+    pub domain_lookup_end: f64,
+    pub connect_start: f64,
+    pub secure_connection_start: f64,
+    pub connect_end: f64,
+    pub request_start: f64,
+    pub response_start: f64,
+    pub response_end: f64,
+    pub dom_interactive: f64,
+    pub dom_content_loaded_event_start: f64,
+    pub dom_content_loaded_event_end: f64,
+    pub dom_complete: f64,
+    pub load_event_start: f64,
+    pub load_event_end: f64,
+    /// The network protocol, e.g. `h2` or `http/1.1`.
+    pub next_hop_protocol: String,
+    /// Bytes transferred, including headers. 0 if served from the cache.
+    pub transfer_size: u64,
+    pub encoded_body_size: u64,
+    pub decoded_body_size: u64,
+}
+
+impl NavigationTiming {
+    /// The time until the first byte of the response was received.
+    pub fn time_to_first_byte(&self) -> f64 {
+        self.response_start
+    }
+
+    /// The time until the `DOMContentLoaded` event finished.
+    pub fn dom_content_loaded(&self) -> f64 {
+        self.dom_content_loaded_event_end
+    }
+
+    /// The time until the `load` event finished.
+    pub fn load_time(&self) -> f64 {
+        self.load_event_end
+    }
+}
+
+/// A Resource Timing entry, for a resource such as a script, image or fetch request.
+///
+/// All times are in milliseconds relative to the start of the navigation. Fields not
+/// supported by the browser, or hidden for cross-origin resources without a
+/// `Timing-Allow-Origin` header, are 0.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ResourceTiming {
+    /// The resource URL.
+    pub name: String,
+    /// What started the request, e.g. `script`, `img`, `css`, `fetch` or `xmlhttprequest`.
+    pub initiator_type: String,
+    pub start_time: f64,
+    pub duration: f64,
+    pub redirect_start: f64,
+    pub redirect_end: f64,
+    pub fetch_start: f64,
+    pub domain_lookup_start: f64,
+    pub domain_lookup_end: f64,
+    pub connect_start: f64,
+    pub secure_connection_start: f64,
+    pub connect_end: f64,
+    pub request_start: f64,
+    pub response_start: f64,
+    pub response_end: f64,
+    pub next_hop_protocol: String,
+    pub transfer_size: u64,
+    pub encoded_body_size: u64,
+    pub decoded_body_size: u64,
+}
+
+/// The performance entries of the current page.
+///
+/// See [`SessionHandle::performance_timing()`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerformanceTiming {
+    /// `None` if the browser does not support Navigation Timing Level 2.
+    pub navigation: Option<NavigationTiming>,
+    pub resources: Vec<ResourceTiming>,
+    pub first_paint: Option<f64>,
+    pub first_contentful_paint: Option<f64>,
+}
+
+impl PerformanceTiming {
+    /// Get the resources whose URL contains the specified string.
+    pub fn resources_matching(&self, url: &str) -> Vec<&ResourceTiming> {
+        self.resources.iter().filter(|r| r.name.contains(url)).collect()
+    }
+
+    /// The total number of bytes transferred for the document and its resources.
+    pub fn total_transfer_size(&self) -> u64 {
+        let navigation = self.navigation.as_ref().map(|n| n.transfer_size).unwrap_or_default();
+        navigation + self.resources.iter().map(|r| r.transfer_size).sum::<u64>()
+    }
+}
+
+/// Web Vitals for the current page, in milliseconds except for the layout shift score.
+///
+/// A value is `None` if the browser does not support it or it has not happened yet,
+/// e.g. there has been no user interaction.
+///
+/// See [`SessionHandle::web_vitals()`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebVitals {
+    /// Largest Contentful Paint (LCP).
+    pub largest_contentful_paint: Option<f64>,
+    /// Cumulative Layout Shift (CLS). 0 if the browser does not report layout shifts.
+    pub cumulative_layout_shift: f64,
+    /// The longest interaction observed, from input to the next paint. This approximates
+    /// Interaction to Next Paint (INP), which ignores outliers on pages with many
+    /// interactions.
+    pub interaction_to_next_paint: Option<f64>,
+    /// First Contentful Paint (FCP).
+    pub first_contentful_paint: Option<f64>,
+    /// Time to First Byte (TTFB).
+    pub time_to_first_byte: Option<f64>,
+}
+
+/// Limits for [`WebVitals`], to fail a test when a page gets too slow.
+///
+/// Metrics without a limit, and metrics the browser did not report, are not checked.
+///
+/// # Example:
+/// ```ignore
+/// use std::time::Duration;
+/// use thirtyfour::session::performance::PerformanceBudget;
+///
+/// driver.get("http://webappdemo").await?;
+/// driver.web_vitals().await?;
+/// // ...
+/// let budget = PerformanceBudget::new()
+///     .with_max_lcp(Duration::from_millis(2500))
+///     .with_max_cls(0.1);
+/// budget.check(&driver.web_vitals().await?)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PerformanceBudget {
+    max_lcp: Option<Duration>,
+    max_cls: Option<f64>,
+    max_inp: Option<Duration>,
+    max_fcp: Option<Duration>,
+    max_ttfb: Option<Duration>,
+}
+
+impl PerformanceBudget {
+    /// A budget without any limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum Largest Contentful Paint.
+    pub fn with_max_lcp(mut self, max: Duration) -> Self {
+        self.max_lcp = Some(max);
+        self
+    }
+
+    /// Set the maximum Cumulative Layout Shift score.
+    pub fn with_max_cls(mut self, max: f64) -> Self {
+        self.max_cls = Some(max);
+        self
+    }
+
+    /// Set the maximum interaction latency.
+    pub fn with_max_inp(mut self, max: Duration) -> Self {
+        self.max_inp = Some(max);
+        self
+    }
+
+    /// Set the maximum First Contentful Paint.
+    pub fn with_max_fcp(mut self, max: Duration) -> Self {
+        self.max_fcp = Some(max);
+        self
+    }
+
+    /// Set the maximum Time to First Byte.
+    pub fn with_max_ttfb(mut self, max: Duration) -> Self {
+        self.max_ttfb = Some(max);
+        self
+    }
+
+    /// Return a `CustomError` listing every metric that exceeds its limit.
+    pub fn check(&self, vitals: &WebVitals) -> WebDriverResult<()> {
+        let mut violations = Vec::new();
+        let mut check_ms = |name: &str, value: Option<f64>, max: Option<Duration>| {
+            if let (Some(value), Some(max)) = (value, max) {
+                let max_ms = max.as_secs_f64() * 1000.0;
+                if value > max_ms {
+                    violations.push(format!("{} {:.0}ms > {:.0}ms", name, value, max_ms));
+                }
+            }
+        };
+        check_ms("LCP", vitals.largest_contentful_paint, self.max_lcp);
+        check_ms("INP", vitals.interaction_to_next_paint, self.max_inp);
+        check_ms("FCP", vitals.first_contentful_paint, self.max_fcp);
+        check_ms("TTFB", vitals.time_to_first_byte, self.max_ttfb);
+        if let Some(max) = self.max_cls {
+            if vitals.cumulative_layout_shift > max {
+                violations.push(format!("CLS {:.3} > {:.3}", vitals.cumulative_layout_shift, max));
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(WebDriverError::CustomError(format!(
+                "performance budget exceeded: {}",
+                violations.join(", ")
+            )))
+        }
+    }
+}
+
+impl SessionHandle {
+    /// Get the Navigation Timing, Resource Timing and paint entries of the current page
+    /// from the browser's Performance API.
+    ///
+    /// **NOTE:** Browsers keep at most 250 resource entries by default.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// driver.get("http://webappdemo").await?;
+    /// let timing = driver.performance_timing().await?;
+    /// if let Some(navigation) = &timing.navigation {
+    ///     println!("Loaded in {}ms", navigation.load_time());
+    /// }
+    /// ```
+    pub async fn performance_timing(&self) -> WebDriverResult<PerformanceTiming> {
+        let ret = self.execute_script(PERFORMANCE_TIMING_SCRIPT, Vec::new()).await?;
+        ret.convert()
+    }
+
+    /// Get the Web Vitals of the current page, starting to observe them if needed.
+    ///
+    /// LCP, CLS and FCP are reported by the browser for the whole page load, so they can
+    /// be read at any time. Only slow interactions are reported retroactively, so call
+    /// this once right after navigating to observe all interactions from then on.
+    /// Observation restarts on each new document.
+    ///
+    /// See [`PerformanceBudget`] to check the values against limits.
+    pub async fn web_vitals(&self) -> WebDriverResult<WebVitals> {
+        let ret = self.execute_script(WEB_VITALS_SCRIPT, Vec::new()).await?;
+        ret.convert()
+    }
+
+    /// Get the current values of the browser's runtime metrics via the CDP `Performance`
+    /// domain, e.g. `JSHeapUsedSize`, `LayoutCount` or `ScriptDuration`.
+    ///
+    /// This requires a Chromium-based browser.
+    pub async fn performance_metrics(&self) -> WebDriverResult<HashMap<String, f64>> {
+        if !self.is_chromium() {
+            return Err(WebDriverError::CustomError(
+                "performance metrics require a Chromium-based browser".to_string(),
+            ));
+        }
+        let dev_tools = ChromeDevTools::new(self.clone());
+        dev_tools.execute_cdp_command(&performance::Enable::default()).await?;
+        let ret = dev_tools.execute_cdp_command(&performance::GetMetrics {}).await?;
+        Ok(ret.metrics.into_iter().map(|metric| (metric.name, metric.value)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_navigation_timing() {
+        let timing: PerformanceTiming = serde_json::from_value(json!({
+            "navigation": {
+                "name": "http://webappdemo/",
+                "entryType": "navigation",
+                "type": "navigate",
+                "responseStart": 42.5,
+                "loadEventEnd": 300.0,
+                "transferSize": 1000
+            },
+            "resources": [{"name": "http://webappdemo/app.js", "initiatorType": "script", "transferSize": 500}],
+            "firstPaint": null,
+            "firstContentfulPaint": 120.0
+        }))
+        .unwrap();
+        let navigation = timing.navigation.as_ref().unwrap();
+        assert_eq!(navigation.navigation_type, "navigate");
+        assert_eq!(navigation.time_to_first_byte(), 42.5);
+        assert_eq!(navigation.load_time(), 300.0);
+        assert_eq!(timing.resources_matching("app.js").len(), 1);
+        assert_eq!(timing.total_transfer_size(), 1500);
+    }
+
+    #[test]
+    fn check_budget() {
+        let vitals = WebVitals {
+            largest_contentful_paint: Some(3000.0),
+            cumulative_layout_shift: 0.05,
+            interaction_to_next_paint: None,
+            first_contentful_paint: Some(900.0),
+            time_to_first_byte: None,
+        };
+        let budget = PerformanceBudget::new()
+            .with_max_cls(0.1)
+            .with_max_inp(Duration::from_millis(200))
+            .with_max_fcp(Duration::from_secs(1));
+        assert!(budget.check(&vitals).is_ok());
+
+        let err = budget.with_max_lcp(Duration::from_millis(2500)).check(&vitals).unwrap_err();
+        assert!(err.to_string().contains("LCP 3000ms > 2500ms"), "{}", err);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::common::connector::SessionConnector;
 use crate::error::WebDriverResult;
 use crate::session::handle::SessionHandle;
 use std::ops::{Deref, DerefMut};
@@ -54,14 +55,24 @@ impl WebDriver {
     {
         use fantoccini::ClientBuilder;
         let caps: Capabilities = capabilities.into();
-        let client = ClientBuilder::native().capabilities(caps.clone()).connect(server_url).await?;
+        let connector = SessionConnector::new();
+        let client = ClientBuilder::new(connector.clone())
+            .capabilities(caps.clone())
+            .connect(server_url)
+            .await?;
+        let session_caps = connector.take_session_capabilities();
 
         // Set default timeouts.
         let timeouts = TimeoutConfiguration::default();
         client.update_timeouts(timeouts).await?;
 
+        let handle = SessionHandle::new(client, caps).await?;
+        handle.config.set_server_url(server_url);
+        if let Some(url) = session_caps["webSocketUrl"].as_str() {
+            handle.config.set_websocket_url(url);
+        }
         Ok(Self {
-            handle: SessionHandle::new(client, caps).await?,
+            handle,
         })
     }
 
@@ -1,17 +1,183 @@
 use fantoccini::elements::{Element, ElementRef};
 use fantoccini::error::CmdError;
+use fantoccini::key::Key;
 use serde::ser::{Serialize, Serializer};
+use serde::Deserialize;
 use serde_json::Value;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
 use std::ops::{Deref, DerefMut};
 use std::path::Path;
+use std::time::Duration;
 use tokio::fs::File;
 use tokio::io::AsyncWriteExt;
 
+use crate::common::command::W3CCommand;
 use crate::error::WebDriverError;
+use crate::query::ElementPollerTicker;
 use crate::session::handle::SessionHandle;
+use crate::support::sleep;
+use crate::ElementSnapshot;
 use crate::{common::types::ElementRect, error::WebDriverResult, By, ElementRefHelper};
+use crate::{Shortcut, ShortcutKey};
+
+/// Fire the HTML5 drag-and-drop events from `source` to `target`, sharing a
+/// single `DataTransfer` object, in the same order the browser would.
+const HTML5_DRAG_SCRIPT: &str = r#"
+    const source = arguments[0];
+    const target = arguments[1];
+    const dataTransfer = new DataTransfer();
+    const fire = (elem, type) => {
+        const rect = elem.getBoundingClientRect();
+        elem.dispatchEvent(new DragEvent(type, {
+            bubbles: true,
+            cancelable: true,
+            composed: true,
+            dataTransfer: dataTransfer,
+            clientX: rect.left + rect.width / 2,
+            clientY: rect.top + rect.height / 2
+        }));
+    };
+    fire(source, "dragstart");
+    fire(source, "drag");
+    fire(target, "dragenter");
+    fire(target, "dragover");
+    fire(target, "drop");
+    fire(source, "dragend");
+"#;
+
+/// Add a hidden file input to the document, for `drag_files_onto()`.
+const FILE_INPUT_SCRIPT: &str = r#"
+    const input = document.createElement("input");
+    input.type = "file";
+    input.multiple = true;
+    input.style.position = "fixed";
+    input.style.left = "-1000px";
+    input.style.opacity = "0";
+    document.body.appendChild(input);
+    return input;
+"#;
+
+/// Drop the files selected in the given file input onto `target`.
+const FILE_DROP_SCRIPT: &str = r#"
+    const input = arguments[0];
+    const target = arguments[1];
+    const dataTransfer = new DataTransfer();
+    for (const file of input.files) {
+        dataTransfer.items.add(file);
+    }
+    const rect = target.getBoundingClientRect();
+    for (const type of ["dragenter", "dragover", "drop"]) {
+        target.dispatchEvent(new DragEvent(type, {
+            bubbles: true,
+            cancelable: true,
+            composed: true,
+            dataTransfer: dataTransfer,
+            clientX: rect.left + rect.width / 2,
+            clientY: rect.top + rect.height / 2
+        }));
+    }
+"#;
+
+/// Remove the file input added by `FILE_INPUT_SCRIPT`.
+const FILE_INPUT_REMOVE_SCRIPT: &str = "arguments[0].remove();";
+
+/// Dispatch the `input` and `change` events, for frameworks that only update their
+/// state from those events (e.g. React-controlled inputs).
+const INPUT_EVENTS_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    elem.dispatchEvent(new Event("input", { bubbles: true }));
+    elem.dispatchEvent(new Event("change", { bubbles: true }));
+"#;
+
+/// Return the element at the specified page coordinates, unless it is the element
+/// itself or one of its descendants. Coordinates outside the viewport return null.
+const ELEMENT_FROM_POINT_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    const root = elem.getRootNode();
+    const doc = root.elementFromPoint ? root : document;
+    const hit = doc.elementFromPoint(arguments[1] - window.scrollX, arguments[2] - window.scrollY);
+    if (!hit || hit === elem || elem.contains(hit)) {
+        return null;
+    }
+    return hit;
+"#;
+
+/// Return true if the element's position or size changes between two animation frames.
+const ELEMENT_MOVING_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    const done = arguments[arguments.length - 1];
+    const before = elem.getBoundingClientRect();
+    requestAnimationFrame(() => requestAnimationFrame(() => {
+        const after = elem.getBoundingClientRect();
+        done(before.x !== after.x || before.y !== after.y ||
+            before.width !== after.width || before.height !== after.height);
+    }));
+"#;
+
+/// Return the element's bounding rectangle relative to the viewport, along with
+/// the viewport size (excluding scrollbars).
+const VIEWPORT_RECT_SCRIPT: &str = r#"
+    const rect = arguments[0].getBoundingClientRect();
+    return {
+        element: { x: rect.left, y: rect.top, width: rect.width, height: rect.height },
+        viewport: {
+            x: 0,
+            y: 0,
+            width: document.documentElement.clientWidth,
+            height: document.documentElement.clientHeight
+        }
+    };
+"#;
+
+#[derive(Debug, Deserialize)]
+struct ViewportRects {
+    element: ElementRect,
+    viewport: ElementRect,
+}
+
+/// Collect the state of each element in `arguments[0]`, for `ElementSnapshot`.
+///
+/// `displayed` uses `checkVisibility()` where available, which is close to (but not
+/// exactly the same as) the algorithm used by `is_displayed()`.
+const SNAPSHOT_SCRIPT: &str = r#"
+    const isDisplayed = elem => {
+        if (elem.checkVisibility) {
+            return elem.checkVisibility({ checkOpacity: true, checkVisibilityCSS: true });
+        }
+        const style = window.getComputedStyle(elem);
+        return style.visibility !== "hidden" && style.display !== "none" &&
+            elem.getClientRects().length > 0;
+    };
+    return arguments[0].map(elem => {
+        const rect = elem.getBoundingClientRect();
+        const attributes = {};
+        for (const attr of elem.attributes) {
+            attributes[attr.name] = attr.value;
+        }
+        return {
+            tagName: elem.tagName.toLowerCase(),
+            text: elem.innerText || "",
+            classNames: Array.from(elem.classList),
+            attributes: attributes,
+            value: typeof elem.value === "string" ? elem.value : null,
+            displayed: isDisplayed(elem),
+            enabled: !elem.matches(":disabled"),
+            selected: !!(elem.checked || elem.selected),
+            rect: {
+                x: rect.left + window.scrollX,
+                y: rect.top + window.scrollY,
+                width: rect.width,
+                height: rect.height
+            }
+        };
+    });
+"#;
+
+/// The number of attempts made by `WebElement::replace_text()`.
+const REPLACE_TEXT_ATTEMPTS: u32 = 3;
+/// The per-key delay added by `WebElement::replace_text()` for each retry.
+const REPLACE_TEXT_RETRY_DELAY: Duration = Duration::from_millis(50);
 
 /// The WebElement struct encapsulates a single element on a page.
 ///
@@ -149,6 +315,91 @@ impl WebElement {
         })
     }
 
+    /// Get the bounding rectangle for this WebElement, relative to the top-left corner
+    /// of the viewport rather than the page.
+    pub async fn viewport_rect(&self) -> WebDriverResult<ElementRect> {
+        Ok(self.viewport_rects().await?.element)
+    }
+
+    /// Return true if any part of this element is within the viewport.
+    ///
+    /// This only considers the element's position. It does not check whether the
+    /// element is displayed or covered by another element.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let header = driver.find_element(By::Id("sticky-header")).await?;
+    /// driver.action_chain().scroll_by(0, 2000).perform().await?;
+    /// assert!(header.is_in_viewport().await?);
+    /// ```
+    pub async fn is_in_viewport(&self) -> WebDriverResult<bool> {
+        Ok(self.visible_fraction().await? > 0.0)
+    }
+
+    /// Return the fraction (from 0.0 to 1.0) of this element's area that is within the
+    /// viewport. Elements with no area return 0.0.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let image = driver.find_element(By::Id("lazy-image")).await?;
+    /// if image.visible_fraction().await? >= 0.5 {
+    ///     image.wait_until().has_attribute("data-loaded", "true").await?;
+    /// }
+    /// ```
+    pub async fn visible_fraction(&self) -> WebDriverResult<f64> {
+        let rects = self.viewport_rects().await?;
+        let area = rects.element.area();
+        if area <= 0.0 {
+            return Ok(0.0);
+        }
+        let visible = rects.element.intersection(&rects.viewport).map(|r| r.area()).unwrap_or(0.0);
+        Ok(visible / area)
+    }
+
+    async fn viewport_rects(&self) -> WebDriverResult<ViewportRects> {
+        let ret = self.handle.execute_script(VIEWPORT_RECT_SCRIPT, vec![self.to_json()?]).await?;
+        ret.convert()
+    }
+
+    /// Get the tag name, text, classes, attributes, value, displayed/enabled/selected
+    /// state and rect of this element in a single round trip.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let elem = driver.find_element(By::Id("button1")).await?;
+    /// let snapshot = elem.snapshot().await?;
+    /// assert_eq!(snapshot.tag_name, "button");
+    /// assert!(snapshot.displayed && snapshot.enabled);
+    /// ```
+    pub async fn snapshot(&self) -> WebDriverResult<ElementSnapshot> {
+        let mut snapshots = Self::snapshot_all(std::slice::from_ref(self)).await?;
+        snapshots.pop().ok_or_else(|| WebDriverError::CustomError("no snapshot returned".into()))
+    }
+
+    /// Take a snapshot of each of the specified elements in a single round trip.
+    /// The snapshots are returned in the same order as the elements.
+    ///
+    /// See [`WebElement::snapshot()`] for more details.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let rows = driver.find_elements(By::Css("table tr")).await?;
+    /// let texts: Vec<String> = WebElement::snapshot_all(&rows)
+    ///     .await?
+    ///     .into_iter()
+    ///     .map(|s| s.text)
+    ///     .collect();
+    /// ```
+    pub async fn snapshot_all(elements: &[WebElement]) -> WebDriverResult<Vec<ElementSnapshot>> {
+        let handle = match elements.first() {
+            Some(elem) => &elem.handle,
+            None => return Ok(Vec::new()),
+        };
+        let args = elements.iter().map(|e| e.to_json()).collect::<WebDriverResult<Vec<_>>>()?;
+        let ret = handle.execute_script(SNAPSHOT_SCRIPT, vec![Value::Array(args)]).await?;
+        ret.convert()
+    }
+
     /// Get the tag name for this WebElement.
     ///
     /// # Example:
@@ -272,10 +523,71 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn click(&self) -> WebDriverResult<()> {
+        if self.handle.config.get_settings().interaction.scroll_before_click {
+            self.scroll_into_view().await?;
+        }
         self.element.click().await?;
+        self.handle.slow_motion().await;
         Ok(())
     }
 
+    /// Move the mouse over the center of this element, after scrolling it into view.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let menu = driver.find_element(By::Id("menu")).await?;
+    /// menu.hover().await?;
+    /// ```
+    pub async fn hover(&self) -> WebDriverResult<()> {
+        self.scroll_into_view().await?;
+        self.handle.action_chain().move_to_element_center(self).perform().await
+    }
+
+    /// Double-click this element, after scrolling it into view.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let elem = driver.find_element(By::Id("button1")).await?;
+    /// elem.double_click().await?;
+    /// ```
+    pub async fn double_click(&self) -> WebDriverResult<()> {
+        self.scroll_into_view().await?;
+        self.handle.action_chain().double_click_element(self).perform().await
+    }
+
+    /// Right-click this element, after scrolling it into view. This usually opens
+    /// the context menu.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let elem = driver.find_element(By::Id("button1")).await?;
+    /// elem.right_click().await?;
+    /// ```
+    pub async fn right_click(&self) -> WebDriverResult<()> {
+        self.scroll_into_view().await?;
+        self.handle.action_chain().context_click_element(self).perform().await
+    }
+
+    /// Click at the specified offset from the center of this element, after scrolling
+    /// it into view.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// // Click near the left edge of a slider.
+    /// let slider = driver.find_element(By::Id("slider")).await?;
+    /// let width = slider.rect().await?.width as i64;
+    /// slider.click_at(-width / 2 + 5, 0).await?;
+    /// ```
+    pub async fn click_at(&self, x_offset: i64, y_offset: i64) -> WebDriverResult<()> {
+        self.scroll_into_view().await?;
+        self.handle
+            .action_chain()
+            .move_to_element_with_offset(self, x_offset, y_offset)
+            .click()
+            .perform()
+            .await
+    }
+
     /// Clear the WebElement contents.
     ///
     /// # Example:
@@ -299,7 +611,9 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn clear(&self) -> WebDriverResult<()> {
-        Ok(self.element.clear().await?)
+        self.element.clear().await?;
+        self.handle.slow_motion().await;
+        Ok(())
     }
 
     /// Get the specified property.
@@ -380,6 +694,62 @@ impl WebElement {
         Ok(self.element.css_value(name).await?)
     }
 
+    /// Get the computed WAI-ARIA role of this element, as determined by the browser.
+    ///
+    /// # Example:
+    /// ```rust
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// #         let caps = DesiredCapabilities::chrome();
+    /// #         let driver = WebDriver::new("http://localhost:4444", caps).await?;
+    /// #         driver.get("http://webappdemo").await?;
+    /// let elem = driver.find_element(By::Id("button1")).await?;
+    /// assert_eq!(elem.computed_role().await?, "button");
+    /// #         driver.quit().await?;
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    pub async fn computed_role(&self) -> WebDriverResult<String> {
+        let v = self
+            .handle
+            .client
+            .issue_cmd(W3CCommand::GetElementComputedRole(self.element_id()))
+            .await?;
+        Ok(serde_json::from_value(v)?)
+    }
+
+    /// Get the computed accessible name (label) of this element, as determined by the browser.
+    ///
+    /// # Example:
+    /// ```rust
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// #         let caps = DesiredCapabilities::chrome();
+    /// #         let driver = WebDriver::new("http://localhost:4444", caps).await?;
+    /// #         driver.get("http://webappdemo").await?;
+    /// let elem = driver.find_element(By::Id("button1")).await?;
+    /// assert_eq!(elem.computed_label().await?, "BUTTON 1");
+    /// #         driver.quit().await?;
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    pub async fn computed_label(&self) -> WebDriverResult<String> {
+        let v = self
+            .handle
+            .client
+            .issue_cmd(W3CCommand::GetElementComputedLabel(self.element_id()))
+            .await?;
+        Ok(serde_json::from_value(v)?)
+    }
+
     /// Return true if the WebElement is currently selected, otherwise false.
     pub async fn is_selected(&self) -> WebDriverResult<bool> {
         Ok(self.element.is_selected().await?)
@@ -458,6 +828,83 @@ impl WebElement {
         Ok(self.is_displayed().await? && self.is_enabled().await?)
     }
 
+    /// Return the element covering the center of this element, if any, using
+    /// `document.elementFromPoint()`. This is typically an overlay such as a cookie
+    /// banner, modal backdrop or loading spinner, which would intercept a click.
+    ///
+    /// Returns `None` if the element at the center is this element or one of its
+    /// descendants, or if the center is outside the viewport.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let elem = driver.find_element(By::Id("button1")).await?;
+    /// if let Some(overlay) = elem.is_obscured().await? {
+    ///     println!("button is covered by: {}", overlay.outer_html().await?);
+    /// }
+    /// ```
+    pub async fn is_obscured(&self) -> WebDriverResult<Option<WebElement>> {
+        let (x, y) = self.rect().await?.center();
+        let ret = self
+            .handle
+            .execute_script(ELEMENT_FROM_POINT_SCRIPT, vec![self.to_json()?, x.into(), y.into()])
+            .await?;
+        match ret.value() {
+            Value::Null => Ok(None),
+            _ => ret.get_element().map(Some),
+        }
+    }
+
+    /// Scroll this element into view, wait until it is clickable, not moving and not
+    /// obscured by another element, and then click it. If the click is intercepted
+    /// anyway, keep trying until the configured `ElementPoller` times out.
+    ///
+    /// A `Timeout` error is returned, describing the last reason the element could not
+    /// be clicked, if the click does not succeed in time.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// // Waits for the loading spinner to disappear before clicking.
+    /// let elem = driver.find_element(By::Id("submit")).await?;
+    /// elem.click_when_ready().await?;
+    /// ```
+    pub async fn click_when_ready(&self) -> WebDriverResult<()> {
+        self.scroll_into_view().await?;
+        let mut ticker = ElementPollerTicker::new(self.handle.config.get_query_poller());
+        loop {
+            let reason = if !self.is_clickable().await? {
+                "element is not displayed or not enabled".to_string()
+            } else if self.is_moving().await? {
+                "element is still moving".to_string()
+            } else if let Some(cover) = self.is_obscured().await? {
+                format!("element is obscured by <{}>", cover.tag_name().await.unwrap_or_default())
+            } else {
+                match self.click().await {
+                    Ok(()) => return Ok(()),
+                    Err(WebDriverError::CmdError(CmdError::Standard(e)))
+                        if matches!(
+                            e.error(),
+                            "element click intercepted" | "element not interactable"
+                        ) =>
+                    {
+                        e.to_string()
+                    }
+                    Err(e) => return Err(e),
+                }
+            };
+
+            if !ticker.tick().await {
+                self.handle.save_failure_screenshot("click_when_ready").await;
+                return Err(WebDriverError::Timeout(format!("element not clicked: {}", reason)));
+            }
+        }
+    }
+
+    async fn is_moving(&self) -> WebDriverResult<bool> {
+        let ret =
+            self.handle.execute_script_async(ELEMENT_MOVING_SCRIPT, vec![self.to_json()?]).await?;
+        ret.convert()
+    }
+
     /// Return true if the WebElement is currently (still) present
     /// and not stale.
     ///
@@ -616,7 +1063,122 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn send_keys(&self, keys: impl AsRef<str>) -> WebDriverResult<()> {
-        Ok(self.element.send_keys(keys.as_ref()).await?)
+        self.element.send_keys(keys.as_ref()).await?;
+        self.handle.slow_motion().await;
+        Ok(())
+    }
+
+    /// Press the specified keyboard shortcut, such as `Ctrl+Shift+K`, on this element.
+    ///
+    /// See [`Shortcut`] for the supported key names. Use `Primary` for the platform's
+    /// primary modifier, which is Cmd on macOS and Ctrl elsewhere.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let editor = driver.find_element(By::Id("editor")).await?;
+    /// editor.press_shortcut("Primary+A").await?;
+    /// editor.press_shortcut("Ctrl+Shift+K").await?;
+    /// ```
+    pub async fn press_shortcut(&self, shortcut: impl AsRef<str>) -> WebDriverResult<()> {
+        let shortcut = Shortcut::parse(shortcut.as_ref())?;
+        let primary = if shortcut.keys().contains(&ShortcutKey::Primary) {
+            self.handle.primary_modifier().await?
+        } else {
+            Key::Control
+        };
+        // Modifier keys stay pressed until the end of the key sequence.
+        let keys: String = shortcut.resolve(primary).into_iter().collect();
+        self.send_keys(keys).await
+    }
+
+    /// Type the specified text into this element one key at a time, waiting for
+    /// `delay` after each key. Use this for inputs that drop characters when
+    /// they are typed too quickly.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let elem = driver.find_element(By::Name("date")).await?;
+    /// elem.type_text("2022-01-31", Duration::from_millis(50)).await?;
+    /// ```
+    pub async fn type_text(&self, text: impl AsRef<str>, delay: Duration) -> WebDriverResult<()> {
+        self.type_keys(text.as_ref(), delay).await?;
+        self.handle.slow_motion().await;
+        Ok(())
+    }
+
+    /// Type the text one key at a time, without the slow-motion pause.
+    async fn type_keys(&self, text: &str, delay: Duration) -> WebDriverResult<()> {
+        for c in text.chars() {
+            self.element.send_keys(&c.to_string()).await?;
+            sleep(delay).await;
+        }
+        Ok(())
+    }
+
+    /// Replace the value of this input with the specified value.
+    ///
+    /// Unlike `clear()`, the existing value is removed using select-all and backspace
+    /// so that the page sees real key events. The new value is then typed in, and the
+    /// `input` and `change` events are dispatched, which is required for some
+    /// frameworks, such as React, to pick up the new value.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let elem = driver.find_element(By::Name("input1")).await?;
+    /// elem.set_value("thirtyfour").await?;
+    /// ```
+    pub async fn set_value(&self, value: impl AsRef<str>) -> WebDriverResult<()> {
+        let primary = self.handle.primary_modifier().await?;
+        self.set_value_with_delay(value.as_ref(), primary, None).await
+    }
+
+    /// Replace the value of this input like `set_value()`, then verify that the input
+    /// contains exactly the specified value. If not, try again up to 3 times in total,
+    /// typing more slowly each time.
+    ///
+    /// A `CustomError` is returned if the value still doesn't match after the last attempt.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let elem = driver.find_element(By::Name("date")).await?;
+    /// elem.replace_text("31/01/2022").await?;
+    /// ```
+    pub async fn replace_text(&self, value: impl AsRef<str>) -> WebDriverResult<()> {
+        let value = value.as_ref();
+        let primary = self.handle.primary_modifier().await?;
+        let mut actual = None;
+        for attempt in 0..REPLACE_TEXT_ATTEMPTS {
+            let delay = (attempt > 0).then(|| REPLACE_TEXT_RETRY_DELAY * attempt);
+            self.set_value_with_delay(value, primary, delay).await?;
+            actual = self.get_property("value").await?;
+            if actual.as_deref() == Some(value) {
+                return Ok(());
+            }
+        }
+
+        Err(WebDriverError::CustomError(format!(
+            "value mismatch after {} attempts: expected '{}', got '{}'",
+            REPLACE_TEXT_ATTEMPTS,
+            value,
+            actual.unwrap_or_default()
+        )))
+    }
+
+    async fn set_value_with_delay(
+        &self,
+        value: &str,
+        primary: Key,
+        delay: Option<Duration>,
+    ) -> WebDriverResult<()> {
+        // Key::Null releases the modifier before deleting the selection.
+        self.element.send_keys(&(primary + "a" + Key::Null + Key::Backspace)).await?;
+        match delay {
+            Some(delay) => self.type_keys(value, delay).await?,
+            None => self.element.send_keys(value).await?,
+        }
+        self.handle.execute_script(INPUT_EVENTS_SCRIPT, vec![self.to_json()?]).await?;
+        self.handle.slow_motion().await;
+        Ok(())
     }
 
     /// Take a screenshot of this WebElement and return it as PNG bytes.
@@ -685,6 +1247,60 @@ impl WebElement {
         Ok(())
     }
 
+    /// Drag this element onto the target element using HTML5 drag-and-drop events.
+    ///
+    /// Pointer actions (e.g. `ActionChain::drag_and_drop_element()`) do not fire the HTML5
+    /// `dragstart`/`drop` events in most browsers. This method dispatches the full event
+    /// sequence via JavaScript instead, with a shared `DataTransfer` object, so it works
+    /// with pages that use the HTML5 drag-and-drop API.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let card = driver.find_element(By::Id("card1")).await?;
+    /// let column = driver.find_element(By::Id("done")).await?;
+    /// card.html5_drag_to(&column).await?;
+    /// ```
+    pub async fn html5_drag_to(&self, target: &WebElement) -> WebDriverResult<()> {
+        self.handle
+            .execute_script(HTML5_DRAG_SCRIPT, vec![self.to_json()?, target.to_json()?])
+            .await?;
+        self.handle.slow_motion().await;
+        Ok(())
+    }
+
+    /// Drop the specified files onto this element, as if they were dragged in from
+    /// the desktop. Use this for drop zones that don't have a file input.
+    ///
+    /// The files are first selected in a temporary (hidden) file input, so the paths
+    /// must be absolute paths on the machine running the browser.
+    ///
+    /// # Example:
+    /// ```ignore
+    /// let dropzone = driver.find_element(By::Id("dropzone")).await?;
+    /// dropzone.drag_files_onto(&["/tmp/report.pdf", "/tmp/photo.png"]).await?;
+    /// ```
+    pub async fn drag_files_onto<P: AsRef<Path>>(&self, paths: &[P]) -> WebDriverResult<()> {
+        let ret = self.handle.execute_script(FILE_INPUT_SCRIPT, Vec::new()).await?;
+        let input = ret.get_element()?;
+        let paths: Vec<String> = paths.iter().map(|p| p.as_ref().display().to_string()).collect();
+        let dropped = async {
+            input.element.send_keys(&paths.join("\n")).await?;
+            self.handle
+                .execute_script(FILE_DROP_SCRIPT, vec![input.to_json()?, self.to_json()?])
+                .await?;
+            Ok::<_, WebDriverError>(())
+        }
+        .await;
+
+        // Remove the input even if the drop failed, but report the original error first.
+        let removed =
+            self.handle.execute_script(FILE_INPUT_REMOVE_SCRIPT, vec![input.to_json()?]).await;
+        dropped?;
+        removed?;
+        self.handle.slow_motion().await;
+        Ok(())
+    }
+
     /// Get the innerHtml property of this element.
     ///
     /// # Example:
@@ -760,3 +1376,41 @@ impl Serialize for WebElement {
         self.element.serialize(serializer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::fakedriver::FakeWebDriver;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn drag_files_onto_removes_input_on_error() {
+        let server = FakeWebDriver::start_with(|req| match req.path.as_str() {
+            "element" => Some((200, json!({"element-6066-11e4-a52e-4f735466cecf": "dropzone"}))),
+            "execute/sync" if req.body["script"] == FILE_INPUT_SCRIPT => {
+                Some((200, json!({"element-6066-11e4-a52e-4f735466cecf": "input"})))
+            }
+            "element/input/value" => Some((
+                400,
+                json!({"error": "invalid argument", "message": "File not found", "stacktrace": ""}),
+            )),
+            _ => None,
+        })
+        .await;
+        let driver = server.connect().await;
+        let dropzone = driver.find_element(By::Id("dropzone")).await.unwrap();
+
+        assert!(dropzone.drag_files_onto(&["/missing.txt"]).await.is_err());
+        let scripts: Vec<Value> = server
+            .requests()
+            .into_iter()
+            .filter(|r| r.path == "execute/sync")
+            .map(|r| r.body["script"].clone())
+            .collect();
+        assert_eq!(scripts, vec![json!(FILE_INPUT_SCRIPT), json!(FILE_INPUT_REMOVE_SCRIPT)]);
+        assert_eq!(
+            server.last_body("POST", "execute/sync").unwrap()["args"],
+            json!([{"element-6066-11e4-a52e-4f735466cecf": "input"}])
+        );
+    }
+}